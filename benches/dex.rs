@@ -0,0 +1,73 @@
+//! Compares lookups in a [Dex] backed by a hash map with a [DenseDex].
+//!
+//! Run with `cargo bench --bench dex`.
+
+use std::{hint::black_box, time::Instant};
+
+use firecore_pokedex::{
+    pokemon::{
+        data::{Breeding, Training},
+        stat::StatSet,
+        Pokemon, PokemonId,
+    },
+    types::{PokemonType, PokemonTypes},
+    DenseDex, Dex, DexStorage, Identifiable,
+};
+
+const POKEMON: u16 = 1025;
+const ROUNDS: usize = 2000;
+
+fn pokemon(id: u16) -> Pokemon {
+    Pokemon {
+        id: PokemonId(id),
+        name: format!("Pokemon {}", id),
+        types: PokemonTypes {
+            primary: PokemonType::Normal,
+            secondary: None,
+        },
+        moves: Vec::new(),
+        learnset: Default::default(),
+        base: StatSet::uniform(60),
+        species: "Bench".to_owned(),
+        evolution: None,
+        height: 10,
+        weight: 100,
+        training: Training {
+            base_exp: 100,
+            growth: Default::default(),
+        },
+        breeding: Breeding { gender: None },
+    }
+}
+
+fn bench<S: DexStorage<Pokemon>>(name: &str, dex: &Dex<Pokemon, S>, ids: &[PokemonId]) {
+    let start = Instant::now();
+    let mut found = 0usize;
+    for _ in 0..ROUNDS {
+        for id in ids {
+            if let Some(pokemon) = dex.try_get(black_box(id)) {
+                found += pokemon.id().0 as usize;
+            }
+        }
+    }
+    let elapsed = start.elapsed();
+    black_box(found);
+    println!(
+        "{:<8} {:>8.2} ns/lookup",
+        name,
+        elapsed.as_nanos() as f64 / (ROUNDS * ids.len()) as f64
+    );
+}
+
+fn main() {
+    let hashed = (0..=POKEMON).map(pokemon).collect::<Dex<Pokemon>>();
+    let dense = (0..=POKEMON).map(pokemon).collect::<DenseDex<Pokemon>>();
+
+    // Look up ids in a scattered order, including some that are missing.
+    let ids = (0..=POKEMON + 100)
+        .map(|i| PokemonId(i.wrapping_mul(389) % (POKEMON + 100)))
+        .collect::<Vec<_>>();
+
+    bench("hashed", &hashed, &ids);
+    bench("dense", &dense, &ids);
+}
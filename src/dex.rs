@@ -1,17 +1,62 @@
-use alloc::sync::Arc;
-use core::{hash::Hash, ops::Deref};
+use alloc::{string::String, sync::Arc, vec::Vec};
+use core::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::Deref,
+};
+use hashbrown::HashMap;
 use serde::{Serialize, Deserializer, Deserialize, Serializer};
 
 use crate::Identifiable;
 
+#[cfg(feature = "diff")]
+mod diff;
+#[cfg(feature = "diff")]
+pub use diff::*;
+
+mod layered;
+pub use layered::*;
+
+mod query;
+pub use query::*;
+
+mod search;
+pub use search::*;
+
+mod storage;
+pub use storage::*;
+
 /// A Dex is used to hold types with an identifiable value (see [Identifiable]).
+///
+/// Values can also be found by name.
+/// Names are matched ignoring case, spaces and punctuation, so "Mr. Mime" and "mr-mime" are the same.
+///
+/// Values are kept in a [DexStorage], which is a hash map by default.
+/// Use [DenseDex] for numeric identifiers.
+///
+/// [Dex::iter], [Dex::values] and [Dex::ids] iterate in the order of the storage,
+/// which is no particular order for a [HashStorage] and identifier order for a [DenseStorage].
+/// [Dex::iter_sorted], [Dex::values_sorted] and [Dex::ids_sorted] are always sorted by identifier,
+/// but sort every value each time they are called.
+/// Serializing a Dex always writes its values sorted by identifier.
 #[derive(Debug, Clone)]
-pub struct Dex<I: Identifiable>(pub hashbrown::HashMap<I::Id, Arc<I>>);
+pub struct Dex<I: Identifiable, S = HashStorage<I>> {
+    values: S,
+    /// Normalized names and the values that have them, with the most recently inserted last.
+    /// Names without any letters or numbers are not indexed.
+    names: HashMap<String, Vec<I::Id>>,
+}
+
+/// A [Dex] that keeps its values in an array indexed by their identifier (see [DenseStorage]).
+pub type DenseDex<I> = Dex<I, DenseStorage<I>>;
 
-impl<I: Identifiable> Dex<I> where I::Id: Eq + Hash {
+impl<I: Identifiable, S: DexStorage<I>> Dex<I, S>
+where
+    I::Id: Eq + Hash,
+{
     /// Try to get an identifiable value from the Dex.
     pub fn try_get(&self, id: &I::Id) -> Option<&Arc<I>> {
-        self.0.get(id)
+        self.values.get(id)
     }
 
     /// Get the unknown value from the Dex.
@@ -25,14 +70,24 @@ impl<I: Identifiable> Dex<I> where I::Id: Eq + Hash {
         })
     }
 
+    /// Check if the Dex has its unknown value, which [Dex::unknown] needs.
+    pub fn has_unknown(&self) -> bool {
+        self.try_get(&I::UNKNOWN).is_some()
+    }
+
     /// Get the identifiable value from the Dex, or return the unknown value.
     pub fn get(&self, id: &I::Id) -> &Arc<I> {
         self.try_get(id).unwrap_or_else(|| self.unknown())
     }
 
+    /// Check if the Dex has a value.
+    pub fn contains(&self, id: &I::Id) -> bool {
+        self.values.get(id).is_some()
+    }
+
     /// Get the length of the Dex.
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.values.len()
     }
 
     /// Check if the Dex is empty
@@ -40,66 +95,372 @@ impl<I: Identifiable> Dex<I> where I::Id: Eq + Hash {
         self.len() == 0
     }
 
-    pub fn new(inner: hashbrown::HashMap<I::Id, Arc<I>>) -> Self {
-        Self(inner)
+    /// Iterate over the identifiers and values in the Dex, in the order of its storage (see [Dex]).
+    pub fn iter(&self) -> impl Iterator<Item = (&I::Id, &Arc<I>)> + '_ {
+        self.values().map(|v| (v.id(), v))
+    }
+
+    /// Iterate over the values in the Dex, in the order of its storage (see [Dex]).
+    pub fn values(&self) -> S::Values<'_> {
+        self.values.values()
+    }
+
+    /// Iterate over the identifiers in the Dex, in the order of its storage (see [Dex]).
+    pub fn ids(&self) -> impl Iterator<Item = &I::Id> + '_ {
+        self.values().map(|v| v.id())
+    }
+
+    /// Iterate over the identifiers and values in the Dex, sorted by identifier.
+    pub fn iter_sorted(&self) -> impl Iterator<Item = (&I::Id, &Arc<I>)> + '_
+    where
+        I::Id: Ord,
+    {
+        self.values_sorted().map(|v| (v.id(), v))
+    }
+
+    /// Iterate over the values in the Dex, sorted by identifier.
+    pub fn values_sorted(&self) -> impl Iterator<Item = &Arc<I>> + '_
+    where
+        I::Id: Ord,
+    {
+        let mut values = self.values().collect::<Vec<_>>();
+        values.sort_by(|a, b| a.id().cmp(b.id()));
+        values.into_iter()
+    }
+
+    /// Iterate over the identifiers in the Dex, sorted.
+    pub fn ids_sorted(&self) -> impl Iterator<Item = &I::Id> + '_
+    where
+        I::Id: Ord,
+    {
+        self.values_sorted().map(|v| v.id())
+    }
+
+    pub fn new(inner: HashMap<I::Id, Arc<I>>) -> Self
+    where
+        I::Id: Clone,
+    {
+        inner.into_values().collect()
+    }
+
+    /// Move the values of this Dex into a different [DexStorage].
+    pub fn into_storage<T: DexStorage<I>>(self) -> Dex<I, T> {
+        let mut values = T::default();
+        for v in self.values.into_values() {
+            values.insert(v);
+        }
+        Dex {
+            values,
+            names: self.names,
+        }
     }
 
     pub fn insert(&mut self, v: I) -> Option<Arc<I>>
     where
         I::Id: Clone,
     {
-        self.0.insert(v.id().clone(), Arc::from(v))
+        self.insert_arc(Arc::new(v))
+    }
+
+    /// Insert a value that is already shared.
+    pub fn insert_arc(&mut self, v: Arc<I>) -> Option<Arc<I>>
+    where
+        I::Id: Clone,
+    {
+        let id = v.id().clone();
+        let name = normalize_name(v.name());
+        let previous = self.values.insert(v);
+        if let Some(previous) = &previous {
+            self.remove_name(previous);
+        }
+        if !name.is_empty() {
+            self.names.entry(name).or_default().push(id);
+        }
+        previous
     }
 
+    /// Get a value by its name. See [Dex] for how names are matched.
+    /// Names without any letters or numbers never match.
     pub fn try_get_named(&self, name: impl AsRef<str>) -> Option<&Arc<I>> {
-        let name = name.as_ref();
-        self.0
-            .values()
-            .find(|i| i.name().eq_ignore_ascii_case(name))
+        let name = normalize_name(name.as_ref());
+        if name.is_empty() {
+            return None;
+        }
+        self.names
+            .get(&name)
+            .and_then(|ids| ids.last())
+            .and_then(|id| self.try_get(id))
     }
 
-    pub fn remove(&mut self, id: &I::Id) -> Option<Arc<I>> {
-        self.0.remove(id)
+    pub fn remove(&mut self, id: &I::Id) -> Option<Arc<I>>
+    where
+        I::Id: Clone,
+    {
+        let removed = self.values.remove(id)?;
+        self.remove_name(&removed);
+        Some(removed)
+    }
+
+    /// Remove a value's name from the name index,
+    /// leaving it to any other value with the same name.
+    fn remove_name(&mut self, removed: &I) {
+        let name = normalize_name(removed.name());
+        if let Some(ids) = self.names.get_mut(&name) {
+            ids.retain(|id| id != removed.id());
+            if ids.is_empty() {
+                self.names.remove(&name);
+            }
+        }
     }
 }
 
+/// Normalize a name for lookups by lowercasing it and removing everything but letters and numbers.
+/// Gender symbols become letters first, so "Nidoran♀" and "Nidoran♂" stay different.
+pub fn normalize_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '♀' => 'f',
+            '♂' => 'm',
+            c => c,
+        })
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
 fn name<T: ?Sized>() -> &'static str {
     let name = core::any::type_name::<T>();
     name.split("::").last().unwrap_or(name)
 }
 
-impl<I: Identifiable> Default for Dex<I>
+impl<I: Identifiable, S: Default> Default for Dex<I, S>
 where
     I::Id: Hash + Eq,
 {
     fn default() -> Self {
-        Self(Default::default())
+        Self {
+            values: Default::default(),
+            names: Default::default(),
+        }
     }
 }
 
-/// Serialize Dex as a Vec
-impl<I: Identifiable + Serialize> Serialize for Dex<I>
+impl<I: Identifiable, S: DexStorage<I>> FromIterator<I> for Dex<I, S>
 where
-    I::Id: Hash + Eq,
+    I::Id: Hash + Eq + Clone,
+{
+    fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+        iter.into_iter().map(Arc::new).collect()
+    }
+}
+
+impl<I: Identifiable, S: DexStorage<I>> FromIterator<Arc<I>> for Dex<I, S>
+where
+    I::Id: Hash + Eq + Clone,
+{
+    fn from_iter<T: IntoIterator<Item = Arc<I>>>(iter: T) -> Self {
+        let mut dex = Self::default();
+        for v in iter {
+            dex.insert_arc(v);
+        }
+        dex
+    }
+}
+
+/// Serialize Dex as a Vec sorted by identifier
+impl<I: Identifiable + Serialize, S: DexStorage<I>> Serialize for Dex<I, S>
+where
+    I::Id: Hash + Eq + Ord,
 {
-    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        serializer.collect_seq(self.0.values().map(Deref::deref))
+    fn serialize<Se: Serializer>(&self, serializer: Se) -> Result<Se::Ok, Se::Error> {
+        serializer.collect_seq(self.values_sorted().map(Deref::deref))
     }
 }
 
-/// Deserialize Dex from a Vec
-impl<'de, I: Identifiable + Deserialize<'de>>
-    Deserialize<'de> for Dex<I>
+/// Two values in a list that have the same identifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateId<Id> {
+    pub id: Id,
+    /// The position of the first value with the identifier.
+    pub first: usize,
+    /// The position of the value that has the same identifier.
+    pub second: usize,
+}
+
+impl<Id: Debug> Display for DuplicateId<Id> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Duplicate id {:?} at positions {} and {}",
+            self.id, self.first, self.second
+        )
+    }
+}
+
+impl<I: Identifiable, S: DexStorage<I>> Dex<I, S>
 where
     I::Id: Hash + Eq + Clone,
+{
+    /// Create a Dex from a list, failing if two values have the same identifier.
+    pub fn try_from_vec(values: Vec<I>) -> Result<Self, DuplicateId<I::Id>> {
+        let (dex, duplicates) = Self::from_vec_lenient(values);
+        match duplicates.into_iter().next() {
+            Some(duplicate) => Err(duplicate),
+            None => Ok(dex),
+        }
+    }
+
+    /// Create a Dex from a list, keeping the last value for each identifier.
+    /// Returns every value that had the same identifier as an earlier one.
+    pub fn from_vec_lenient(values: Vec<I>) -> (Self, Vec<DuplicateId<I::Id>>) {
+        let mut dex = Self::default();
+        let mut positions = HashMap::with_capacity(values.len());
+        let mut duplicates = Vec::new();
+        for (second, v) in values.into_iter().enumerate() {
+            if let Some(first) = positions.get(v.id()) {
+                duplicates.push(DuplicateId {
+                    id: v.id().clone(),
+                    first: *first,
+                    second,
+                });
+            } else {
+                positions.insert(v.id().clone(), second);
+            }
+            dex.insert(v);
+        }
+        (dex, duplicates)
+    }
+}
+
+/// Deserialize Dex from a Vec, failing if two values have the same identifier.
+/// Use [LenientDex] to allow duplicates.
+impl<'de, I: Identifiable + Deserialize<'de>, S: DexStorage<I>> Deserialize<'de> for Dex<I, S>
+where
+    I::Id: Hash + Eq + Clone + Debug,
 {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        alloc::vec::Vec::<I>::deserialize(deserializer).map(|i| {
-            Self(
-                i.into_iter()
-                    .map(|i| (i.id().clone(), Arc::new(i)))
-                    .collect(),
-            )
+        Vec::<I>::deserialize(deserializer)
+            .and_then(|values| Self::try_from_vec(values).map_err(serde::de::Error::custom))
+    }
+}
+
+/// A [Dex] that deserializes even when values have the same identifier.
+/// The last value with an identifier is kept.
+#[derive(Debug, Clone)]
+pub struct LenientDex<I: Identifiable, S = HashStorage<I>> {
+    pub dex: Dex<I, S>,
+    pub duplicates: Vec<DuplicateId<I::Id>>,
+}
+
+impl<'de, I: Identifiable + Deserialize<'de>, S: DexStorage<I>> Deserialize<'de>
+    for LenientDex<I, S>
+where
+    I::Id: Hash + Eq + Clone,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<I>::deserialize(deserializer).map(|values| {
+            let (dex, duplicates) = Dex::from_vec_lenient(values);
+            Self { dex, duplicates }
         })
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+
+    use alloc::string::ToString;
+
+    use crate::moves::{Move, MoveId};
+
+    use super::{normalize_name, Dex};
+
+    pub(crate) fn test_move(id: &str, name: &str) -> Move {
+        Move {
+            id: id.parse().unwrap(),
+            name: name.to_string(),
+            category: Default::default(),
+            pokemon_type: Default::default(),
+            power: None,
+            pp: 10,
+        }
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(normalize_name("Mr. Mime"), "mrmime");
+        assert_eq!(normalize_name("mr-mime"), "mrmime");
+        assert_eq!(normalize_name("Farfetch'd"), "farfetchd");
+        assert_eq!(normalize_name("Nidoran♀"), "nidoranf");
+        assert_eq!(normalize_name("Nidoran♂"), "nidoranm");
+
+        let mut dex = Dex::<Move>::default();
+        dex.insert(test_move("mrmime", "Mr. Mime"));
+        dex.insert(test_move("tackle", "Tackle"));
+
+        let id: MoveId = "mrmime".parse().unwrap();
+        assert_eq!(dex.try_get_named("MR MIME").map(|m| m.id), Some(id));
+        assert_eq!(dex.try_get_named("mr-mime").map(|m| m.id), Some(id));
+
+        // replacing an entry without renaming it keeps it in the index
+        dex.insert(test_move("mrmime", "Mr. Mime"));
+        assert_eq!(dex.try_get_named("mr mime").map(|m| m.id), Some(id));
+
+        // renaming an entry updates the index
+        dex.insert(test_move("mrmime", "Mime Jr."));
+        assert!(dex.try_get_named("mr mime").is_none());
+        assert!(dex.try_get_named("mime jr").is_some());
+
+        // removing an entry falls back to another entry with the same name
+        dex.insert(test_move("other", "Tackle"));
+        let tackle = dex.try_get_named("tackle").unwrap().id;
+        dex.remove(&tackle);
+        assert!(dex.try_get_named("tackle").is_some());
+        dex.remove(&dex.try_get_named("tackle").unwrap().id.clone());
+        assert!(dex.try_get_named("tackle").is_none());
+
+        dex.insert(test_move("nidoranf", "Nidoran♀"));
+        dex.insert(test_move("nidoranm", "Nidoran♂"));
+        let female: MoveId = "nidoranf".parse().unwrap();
+        let male: MoveId = "nidoranm".parse().unwrap();
+        assert_eq!(dex.try_get_named("Nidoran♀").map(|m| m.id), Some(female));
+        assert_eq!(dex.try_get_named("Nidoran♂").map(|m| m.id), Some(male));
+
+        // names made only of punctuation are not looked up
+        dex.insert(test_move("question", "???"));
+        assert!(dex.try_get_named("???").is_none());
+        assert!(dex.try_get_named("").is_none());
+    }
+
+    #[test]
+    fn sorted() {
+        let data = r#"[{"id":"ember","name":"Ember","category":"Special","pokemon_type":"Fire","power":40,"pp":25},{"id":"growl","name":"Growl","pokemon_type":"Normal","pp":40},{"id":"tackle","name":"Tackle","category":"Physical","pokemon_type":"Normal","power":40,"pp":35}]"#;
+        let dex = serde_json::from_str::<Dex<Move>>(data).unwrap();
+        assert_eq!(serde_json::to_string(&dex).unwrap(), data);
+        assert_eq!(
+            dex.ids_sorted().map(|id| id.0.as_str()).collect::<alloc::vec::Vec<_>>(),
+            ["ember", "growl", "tackle"]
+        );
+    }
+
+    #[test]
+    fn duplicates() {
+        use super::{DuplicateId, LenientDex};
+
+        let data = r#"[{"id":"ember","name":"Ember","pp":25},{"id":"tackle","name":"Tackle","pp":35},{"id":"ember","name":"Ember","pp":15}]"#;
+
+        let error = serde_json::from_str::<Dex<Move>>(data).unwrap_err();
+        assert!(error.to_string().contains("at positions 0 and 2"));
+
+        let lenient = serde_json::from_str::<LenientDex<Move>>(data).unwrap();
+        assert_eq!(
+            lenient.duplicates,
+            [DuplicateId {
+                id: "ember".parse().unwrap(),
+                first: 0,
+                second: 2,
+            }]
+        );
+        assert_eq!(lenient.dex.len(), 2);
+        assert_eq!(lenient.dex.get(&"ember".parse().unwrap()).pp, 15);
+    }
+}
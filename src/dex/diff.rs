@@ -0,0 +1,228 @@
+//! Differences between two versions of a [Dex].
+
+use alloc::{format, string::String, sync::Arc, vec::Vec};
+use core::{fmt::Display, hash::Hash};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::Identifiable;
+
+use super::{Dex, DexStorage};
+
+/// The differences between an old and new [Dex], sorted by id.
+#[derive(Debug)]
+pub struct DexDiff<'a, I> {
+    /// Values only in the new Dex.
+    pub added: Vec<&'a Arc<I>>,
+    /// Values only in the old Dex.
+    pub removed: Vec<&'a Arc<I>>,
+    /// Values in both that are different, or that could not be compared.
+    pub changed: Vec<ChangedValue<'a, I>>,
+}
+
+/// A value that is different between two versions of a [Dex].
+#[derive(Debug)]
+pub struct ChangedValue<'a, I> {
+    pub old: &'a Arc<I>,
+    pub new: &'a Arc<I>,
+    /// The fields that changed, or why the values could not be compared.
+    pub fields: Result<Vec<FieldChange>, DiffError>,
+}
+
+/// A field that changed, found by its path, such as `base.hp` or `moves[2].level`.
+/// Values are written as JSON, and are [None] when the field was added or removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    pub path: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// A value could not be serialized to compare its fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffError(pub String);
+
+impl<I: Identifiable, S: DexStorage<I>> Dex<I, S>
+where
+    I::Id: Eq + Hash + Ord,
+{
+    /// Compare this Dex to a newer version of it.
+    ///
+    /// Values are compared by their serialized fields.
+    /// Values that cannot be serialized are always listed as changed, with the error.
+    pub fn diff<'a>(&'a self, new: &'a Self) -> DexDiff<'a, I>
+    where
+        I: Serialize,
+    {
+        let added = new
+            .values_sorted()
+            .filter(|v| !self.contains(v.id()))
+            .collect();
+
+        let removed = self
+            .values_sorted()
+            .filter(|v| !new.contains(v.id()))
+            .collect();
+
+        let changed = self
+            .values_sorted()
+            .flat_map(|old| new.try_get(old.id()).map(|new| (old, new)))
+            .flat_map(|(old, new)| {
+                let fields = field_changes(old.as_ref(), new.as_ref());
+                match fields.as_ref().map(Vec::is_empty) {
+                    Ok(true) => None,
+                    _ => Some(ChangedValue { old, new, fields }),
+                }
+            })
+            .collect();
+
+        DexDiff {
+            added,
+            removed,
+            changed,
+        }
+    }
+}
+
+impl<'a, I> DexDiff<'a, I> {
+    /// Check if both versions are the same.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Get the fields that are different between two values.
+pub fn field_changes<T: Serialize>(old: &T, new: &T) -> Result<Vec<FieldChange>, DiffError> {
+    let value = |v: &T| serde_json::to_value(v).map_err(|err| DiffError(format!("{}", err)));
+    let mut changes = Vec::new();
+    compare(String::new(), Some(value(old)?), Some(value(new)?), &mut changes);
+    Ok(changes)
+}
+
+fn compare(path: String, old: Option<Value>, new: Option<Value>, changes: &mut Vec<FieldChange>) {
+    match (old, new) {
+        (Some(Value::Object(mut old)), Some(Value::Object(mut new))) => {
+            let mut keys = old.keys().chain(new.keys()).cloned().collect::<Vec<_>>();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let path = match path.is_empty() {
+                    true => key.clone(),
+                    false => format!("{}.{}", path, key),
+                };
+                compare(path, old.remove(&key), new.remove(&key), changes);
+            }
+        }
+        (Some(Value::Array(old)), Some(Value::Array(new))) if old.len() == new.len() => {
+            for (i, (old, new)) in old.into_iter().zip(new).enumerate() {
+                compare(format!("{}[{}]", path, i), Some(old), Some(new), changes);
+            }
+        }
+        (old, new) => {
+            if old != new {
+                changes.push(FieldChange {
+                    path,
+                    old: old.map(|v| format!("{}", v)),
+                    new: new.map(|v| format!("{}", v)),
+                });
+            }
+        }
+    }
+}
+
+impl Display for FieldChange {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.path,
+            self.old.as_deref().unwrap_or("none"),
+            self.new.as_deref().unwrap_or("none")
+        )
+    }
+}
+
+impl Display for DiffError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Could not serialize value: {}", self.0)
+    }
+}
+
+impl<'a, I: Identifiable> Display for DexDiff<'a, I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for added in &self.added {
+            writeln!(f, "+ {}", added.name())?;
+        }
+        for removed in &self.removed {
+            writeln!(f, "- {}", removed.name())?;
+        }
+        for changed in &self.changed {
+            writeln!(f, "~ {}", changed.new.name())?;
+            match &changed.fields {
+                Ok(fields) => {
+                    for field in fields {
+                        writeln!(f, "    {}", field)?;
+                    }
+                }
+                Err(err) => writeln!(f, "    {}", err)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use alloc::{string::ToString, vec};
+
+    use crate::{dex::tests::test_move, moves::Move, Dex};
+
+    use super::{field_changes, FieldChange};
+
+    #[test]
+    fn diff() {
+        let old = [("tackle", "Tackle"), ("ember", "Ember"), ("growl", "Growl")]
+            .into_iter()
+            .map(|(id, name)| test_move(id, name))
+            .collect::<Dex<Move>>();
+
+        let mut new = old.clone();
+        new.remove(&"growl".parse().unwrap());
+        new.insert(test_move("blaze", "Blaze Kick"));
+        let mut tackle = test_move("tackle", "Tackle");
+        tackle.pp = 35;
+        new.insert(tackle);
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].name, "Blaze Kick");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].name, "Growl");
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(
+            diff.changed[0].fields,
+            Ok(vec![FieldChange {
+                path: "pp".to_string(),
+                old: Some("10".to_string()),
+                new: Some("35".to_string()),
+            }])
+        );
+        assert_eq!(
+            diff.to_string(),
+            "+ Blaze Kick\n- Growl\n~ Tackle\n    pp: 10 -> 35\n"
+        );
+        assert!(old.diff(&old).is_empty());
+
+        struct Broken;
+
+        impl serde::Serialize for Broken {
+            fn serialize<S: serde::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+                Err(serde::ser::Error::custom("broken"))
+            }
+        }
+
+        assert!(field_changes(&Broken, &Broken).is_err());
+    }
+}
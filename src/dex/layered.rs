@@ -0,0 +1,383 @@
+//! Dexes built from a base layer and overlays, such as official data and mod packs.
+
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+use core::hash::Hash;
+
+use hashbrown::HashMap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::Identifiable;
+
+use super::{Dex, DexStorage, HashStorage};
+
+/// A function that changes some fields of a value, or returns why it could not.
+pub type Patch<I> = Box<dyn Fn(&mut I) -> Result<(), String> + Send + Sync>;
+
+/// A change a [Layer] makes to the layers below it.
+pub enum LayerChange<I: Identifiable> {
+    /// Add a value, or replace the value with the same id.
+    Insert(Arc<I>),
+    /// Remove a value.
+    Remove(I::Id),
+    /// Change some fields of a value.
+    Patch(I::Id, Patch<I>),
+}
+
+/// A change in a [Layer] that could not be applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayerError<Id> {
+    /// The value to remove or patch does not exist.
+    Missing(Id),
+    /// A patch changed the identifier of a value, so it was not applied.
+    ChangedId { id: Id, patched: Id },
+    /// A patch could not be applied to a value.
+    Invalid { id: Id, error: String },
+}
+
+/// A named list of changes to apply on top of a [LayeredDex].
+pub struct Layer<I: Identifiable> {
+    pub name: String,
+    pub changes: Vec<LayerChange<I>>,
+}
+
+impl<I: Identifiable> Layer<I> {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            changes: Default::default(),
+        }
+    }
+
+    /// Create a layer that adds or replaces every value in a Dex.
+    pub fn from_dex<S: DexStorage<I>>(name: impl Into<String>, dex: Dex<I, S>) -> Self
+    where
+        I::Id: Eq + Hash,
+    {
+        Self {
+            name: name.into(),
+            changes: dex.values.into_values().into_iter().map(LayerChange::Insert).collect(),
+        }
+    }
+
+    pub fn insert(mut self, value: I) -> Self {
+        self.changes.push(LayerChange::Insert(Arc::new(value)));
+        self
+    }
+
+    pub fn remove(mut self, id: I::Id) -> Self {
+        self.changes.push(LayerChange::Remove(id));
+        self
+    }
+
+    pub fn patch(self, id: I::Id, patch: impl Fn(&mut I) + Send + Sync + 'static) -> Self {
+        self.try_patch(id, move |value| {
+            patch(value);
+            Ok(())
+        })
+    }
+
+    /// Add a patch that can fail, such as one that parses some of its fields.
+    pub fn try_patch(
+        mut self,
+        id: I::Id,
+        patch: impl Fn(&mut I) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.changes.push(LayerChange::Patch(id, Box::new(patch)));
+        self
+    }
+}
+
+/// A [Layer] that can be loaded with serde, such as from a mod pack.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(bound(
+    serialize = "I: Serialize, I::Id: Serialize",
+    deserialize = "I: Deserialize<'de>, I::Id: Deserialize<'de>"
+))]
+pub struct LayerFile<I: Identifiable> {
+    pub name: String,
+    #[serde(default)]
+    pub changes: Vec<LayerEntry<I>>,
+}
+
+/// A change in a [LayerFile].
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[serde(bound(
+    serialize = "I: Serialize, I::Id: Serialize",
+    deserialize = "I: Deserialize<'de>, I::Id: Deserialize<'de>"
+))]
+pub enum LayerEntry<I: Identifiable> {
+    /// Add a value, or replace the value with the same id.
+    Insert(I),
+    /// Remove a value.
+    Remove(I::Id),
+    /// Change some fields of a value with a JSON merge patch ([RFC 7396](https://www.rfc-editor.org/rfc/rfc7396)).
+    /// Fields set to `null` are removed, so they go back to their defaults.
+    #[cfg(feature = "patch")]
+    Patch {
+        id: I::Id,
+        fields: serde_json::Value,
+    },
+}
+
+impl<I: Identifiable + Serialize + DeserializeOwned> From<LayerFile<I>> for Layer<I> {
+    fn from(file: LayerFile<I>) -> Self {
+        file.changes
+            .into_iter()
+            .fold(Layer::new(file.name), |layer, entry| match entry {
+                LayerEntry::Insert(value) => layer.insert(value),
+                LayerEntry::Remove(id) => layer.remove(id),
+                #[cfg(feature = "patch")]
+                LayerEntry::Patch { id, fields } => {
+                    layer.try_patch(id, move |value| patch_fields(value, &fields))
+                }
+            })
+    }
+}
+
+#[cfg(feature = "patch")]
+fn patch_fields<I: Serialize + DeserializeOwned>(
+    value: &mut I,
+    fields: &serde_json::Value,
+) -> Result<(), String> {
+    use alloc::string::ToString;
+
+    let mut merged = serde_json::to_value(&*value).map_err(|e| e.to_string())?;
+    merge_patch(&mut merged, fields);
+    *value = serde_json::from_value(merged).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[cfg(feature = "patch")]
+fn merge_patch(target: &mut serde_json::Value, patch: &serde_json::Value) {
+    use serde_json::Value;
+
+    match patch {
+        Value::Object(fields) => {
+            if !target.is_object() {
+                *target = Value::Object(Default::default());
+            }
+            if let Value::Object(target) = target {
+                for (key, value) in fields {
+                    match value.is_null() {
+                        true => {
+                            target.remove(key);
+                        }
+                        false => {
+                            merge_patch(target.entry(key.clone()).or_insert(Value::Null), value)
+                        }
+                    }
+                }
+            }
+        }
+        patch => *target = patch.clone(),
+    }
+}
+
+/// A [Dex] made of layers, where each layer changes the ones below it.
+/// Remembers which layer each value last came from.
+#[derive(Debug, Clone)]
+pub struct LayeredDex<I: Identifiable, S = HashStorage<I>> {
+    dex: Dex<I, S>,
+    layers: Vec<String>,
+    sources: HashMap<I::Id, usize>,
+}
+
+impl<I: Identifiable, S: DexStorage<I>> LayeredDex<I, S>
+where
+    I::Id: Eq + Hash + Clone,
+{
+    /// Create a layered dex with a base layer.
+    pub fn new(name: impl Into<String>, base: Dex<I, S>) -> Self {
+        let sources = base.ids().map(|id| (id.clone(), 0)).collect();
+        Self {
+            dex: base,
+            layers: alloc::vec![name.into()],
+            sources,
+        }
+    }
+
+    /// Apply a layer on top of the current layers.
+    ///
+    /// Returns every change in the layer that could not be applied.
+    pub fn push(&mut self, layer: Layer<I>) -> Vec<LayerError<I::Id>>
+    where
+        I: Clone,
+    {
+        let index = self.layers.len();
+        self.layers.push(layer.name);
+
+        let mut errors = Vec::new();
+
+        for change in layer.changes {
+            match change {
+                LayerChange::Insert(value) => {
+                    self.sources.insert(value.id().clone(), index);
+                    self.dex.insert_arc(value);
+                }
+                LayerChange::Remove(id) => match self.dex.remove(&id) {
+                    Some(..) => {
+                        self.sources.remove(&id);
+                    }
+                    None => errors.push(LayerError::Missing(id)),
+                },
+                LayerChange::Patch(id, patch) => match self.dex.try_get(&id) {
+                    Some(value) => {
+                        let mut value = I::clone(value);
+                        match patch(&mut value) {
+                            Ok(()) if value.id() == &id => {
+                                self.sources.insert(id, index);
+                                self.dex.insert(value);
+                            }
+                            Ok(()) => errors.push(LayerError::ChangedId {
+                                patched: value.id().clone(),
+                                id,
+                            }),
+                            Err(error) => errors.push(LayerError::Invalid { id, error }),
+                        }
+                    }
+                    None => errors.push(LayerError::Missing(id)),
+                },
+            }
+        }
+
+        errors
+    }
+
+    /// Get the name of the layer a value last came from.
+    pub fn source(&self, id: &I::Id) -> Option<&str> {
+        self.sources
+            .get(id)
+            .map(|index| self.layers[*index].as_str())
+    }
+
+    /// Get the names of the layers, starting with the base layer.
+    pub fn layers(&self) -> &[String] {
+        &self.layers
+    }
+
+    /// Get the merged Dex.
+    pub fn dex(&self) -> &Dex<I, S> {
+        &self.dex
+    }
+
+    pub fn into_dex(self) -> Dex<I, S> {
+        self.dex
+    }
+}
+
+impl<I: Identifiable, S> core::ops::Deref for LayeredDex<I, S> {
+    type Target = Dex<I, S>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.dex
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use alloc::vec::Vec;
+
+    use crate::{
+        dex::tests::test_move,
+        moves::Move,
+        pokemon::{tests::test_pokemon, Pokemon, PokemonId},
+        DenseDex, Dex,
+    };
+
+    use super::{Layer, LayerError, LayerFile, LayeredDex};
+
+    #[test]
+    fn layers() {
+        let base = [("tackle", "Tackle"), ("ember", "Ember"), ("growl", "Growl")]
+            .into_iter()
+            .map(|(id, name)| test_move(id, name))
+            .collect::<Dex<Move>>();
+
+        let mut dex = LayeredDex::new("official", base);
+
+        let errors = dex.push(
+            Layer::<Move>::new("rebalance")
+                .patch("tackle".parse().unwrap(), |m| m.pp = 5)
+                .remove("growl".parse().unwrap())
+                .remove("splash".parse().unwrap()),
+        );
+        assert_eq!(errors, [LayerError::Missing("splash".parse().unwrap())]);
+
+        let errors = dex.push(
+            Layer::<Move>::new("broken")
+                .patch("ember".parse().unwrap(), |m| m.id = "fire".parse().unwrap()),
+        );
+        assert_eq!(
+            errors,
+            [LayerError::ChangedId {
+                id: "ember".parse().unwrap(),
+                patched: "fire".parse().unwrap(),
+            }]
+        );
+        assert!(!dex.contains(&"fire".parse().unwrap()));
+
+        dex.push(Layer::new("fakemon").insert(test_move("blaze", "Blaze Kick")));
+
+        let tackle = "tackle".parse().unwrap();
+        assert_eq!(dex.get(&tackle).pp, 5);
+        assert_eq!(dex.source(&tackle), Some("rebalance"));
+        assert_eq!(dex.source(&"ember".parse().unwrap()), Some("official"));
+        assert_eq!(dex.source(&"blaze".parse().unwrap()), Some("fakemon"));
+        assert_eq!(dex.source(&"growl".parse().unwrap()), None);
+        assert!(dex.try_get_named("blaze kick").is_some());
+        assert_eq!(dex.len(), 3);
+    }
+
+    #[test]
+    fn layer_file() {
+        let base = [(1, "Bulbasaur"), (2, "Ivysaur")]
+            .into_iter()
+            .map(|(id, name)| Pokemon {
+                id: PokemonId(id),
+                name: name.to_owned(),
+                ..test_pokemon(Vec::new())
+            })
+            .collect::<DenseDex<Pokemon>>();
+
+        let mut dex = LayeredDex::new("official", base);
+
+        let file = serde_json::from_str::<LayerFile<Pokemon>>(
+            r#"{"name":"fakemon","changes":[{"remove":2},{"remove":3}]}"#,
+        )
+        .unwrap();
+        let errors = dex.push(Layer::from(file));
+        assert_eq!(errors, [LayerError::Missing(PokemonId(3))]);
+        assert!(!dex.contains(&PokemonId(2)));
+        assert_eq!(dex.source(&PokemonId(1)), Some("official"));
+
+        assert!(serde_json::from_str::<LayerFile<Pokemon>>(
+            r#"{"name":"typo","changes":[{"remvoe":1}]}"#
+        )
+        .is_err());
+
+        #[cfg(feature = "patch")]
+        {
+            let file = serde_json::from_str::<LayerFile<Pokemon>>(
+                r#"{"name":"rebalance","changes":[
+                    {"patch":{"id":1,"fields":{"height":12,"types":{"secondary":null}}}},
+                    {"patch":{"id":1,"fields":{"weight":"heavy"}}}
+                ]}"#,
+            )
+            .unwrap();
+            let errors = dex.push(Layer::from(file));
+            assert!(matches!(
+                errors.as_slice(),
+                [LayerError::Invalid { id: PokemonId(1), .. }]
+            ));
+
+            let bulbasaur = dex.get(&PokemonId(1));
+            assert_eq!(bulbasaur.height, 12);
+            assert_eq!(bulbasaur.types.secondary, None);
+            assert_eq!(bulbasaur.name, "Bulbasaur");
+            assert_eq!(dex.source(&PokemonId(1)), Some("rebalance"));
+        }
+    }
+}
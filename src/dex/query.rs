@@ -0,0 +1,153 @@
+//! Filtering the values in a [Dex].
+
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{hash::Hash, ops::Not};
+
+use crate::Identifiable;
+
+use super::{Dex, DexStorage};
+
+/// A condition a value must match.
+type Filter<'a, I> = Box<dyn Fn(&I) -> bool + 'a>;
+
+/// A list of conditions that values in a [Dex] must all match.
+///
+/// Typed conditions for [Pokemon](crate::pokemon::Pokemon) and [Move](crate::moves::Move)
+/// are in their modules.
+pub struct Query<'a, I> {
+    filters: Vec<Filter<'a, I>>,
+}
+
+impl<'a, I: 'a> Query<'a, I> {
+    /// Create a query that matches every value.
+    pub fn new() -> Self {
+        Self {
+            filters: Default::default(),
+        }
+    }
+
+    /// Only match values that pass a filter.
+    pub fn filter(mut self, filter: impl Fn(&I) -> bool + 'a) -> Self {
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    /// Match values that match this query or another one.
+    pub fn or(self, other: Self) -> Self {
+        Self::new().filter(move |v| self.matches(v) || other.matches(v))
+    }
+
+    /// Check if a value matches this query.
+    pub fn matches(&self, value: &I) -> bool {
+        self.filters.iter().all(|filter| filter(value))
+    }
+}
+
+/// Match values that do not match this query.
+impl<'a, I: 'a> Not for Query<'a, I> {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        Self::new().filter(move |v| !self.matches(v))
+    }
+}
+
+impl<'a, I: 'a> Default for Query<'a, I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Identifiable, S: DexStorage<I>> Dex<I, S>
+where
+    I::Id: Eq + Hash + Ord,
+{
+    /// Get every value that matches a query, sorted by identifier.
+    pub fn query(&self, query: &Query<'_, I>) -> Vec<&Arc<I>> {
+        self.values_sorted().filter(|v| query.matches(v)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use alloc::{vec, vec::Vec};
+
+    use crate::{
+        dex::tests::test_move,
+        moves::{Move, MoveCategory, MoveId},
+        pokemon::{data::LearnableMove, stat::StatType, tests::test_pokemon, Pokemon, PokemonId},
+        types::{PokemonType, PokemonTypes},
+        Dex,
+    };
+
+    use super::Query;
+
+    #[test]
+    fn query() {
+        let surf: MoveId = "surf".parse().unwrap();
+
+        let pokemon = |id, primary, speed, moves| {
+            let mut pokemon = Pokemon {
+                id: PokemonId(id),
+                types: PokemonTypes {
+                    primary,
+                    secondary: None,
+                },
+                ..test_pokemon(moves)
+            };
+            pokemon.base[StatType::Speed] = speed;
+            pokemon
+        };
+
+        let pokedex = [
+            pokemon(1, PokemonType::Water, 110, vec![LearnableMove(20, surf)]),
+            pokemon(2, PokemonType::Water, 80, vec![LearnableMove(20, surf)]),
+            pokemon(3, PokemonType::Fire, 120, vec![LearnableMove(20, surf)]),
+            pokemon(4, PokemonType::Water, 100, vec![]),
+        ]
+        .into_iter()
+        .collect::<Dex<Pokemon>>();
+
+        let ids = |values: Vec<&alloc::sync::Arc<Pokemon>>| values.iter().map(|p| p.id.0).collect::<Vec<_>>();
+
+        let query = Query::<Pokemon>::new()
+            .pokemon_type(PokemonType::Water)
+            .min_base(StatType::Speed, 100);
+        assert_eq!(ids(pokedex.query(&query)), [1, 4]);
+        assert_eq!(ids(pokedex.query(&query.learns(surf))), [1]);
+
+        let query = Query::<Pokemon>::new()
+            .pokemon_type(PokemonType::Fire)
+            .or(Query::new().max_base(StatType::Speed, 90));
+        assert_eq!(ids(pokedex.query(&query)), [2, 3]);
+        assert_eq!(ids(pokedex.query(&!query)), [1, 4]);
+
+        let attack = |id: &str, category, pokemon_type, power| Move {
+            category,
+            pokemon_type,
+            power,
+            ..test_move(id, id)
+        };
+
+        let movedex = [
+            attack("flareblitz", MoveCategory::Physical, PokemonType::Fire, Some(120)),
+            attack("ember", MoveCategory::Special, PokemonType::Fire, Some(40)),
+            attack("firefang", MoveCategory::Physical, PokemonType::Fire, Some(65)),
+            attack("firepunch", MoveCategory::Physical, PokemonType::Fire, Some(80)),
+            attack("willowisp", MoveCategory::Status, PokemonType::Fire, None),
+        ]
+        .into_iter()
+        .collect::<Dex<Move>>();
+
+        let query = Query::<Move>::new()
+            .category(MoveCategory::Physical)
+            .pokemon_type(PokemonType::Fire)
+            .min_power(80);
+        let moves = movedex.query(&query);
+        assert_eq!(
+            moves.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(),
+            ["firepunch", "flareblitz"]
+        );
+    }
+}
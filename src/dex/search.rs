@@ -0,0 +1,207 @@
+//! Fuzzy and prefix searching over the names in a [Dex].
+
+use alloc::{sync::Arc, vec::Vec};
+use core::hash::Hash;
+
+use crate::Identifiable;
+
+use super::{normalize_name, Dex, DexStorage};
+
+/// A value found by searching a [Dex], with how well its name matched.
+#[derive(Debug)]
+pub struct SearchMatch<'a, I> {
+    pub value: &'a Arc<I>,
+    /// How close the name is to the search, from 0 to [SearchMatch::EXACT].
+    pub score: u8,
+}
+
+impl<'a, I> Clone for SearchMatch<'a, I> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, I> Copy for SearchMatch<'a, I> {}
+
+impl<'a, I> SearchMatch<'a, I> {
+    /// The score of a name that matches the search exactly.
+    pub const EXACT: u8 = 100;
+
+    /// The lowest score [Dex::suggest] accepts.
+    pub const SUGGEST: u8 = 50;
+}
+
+impl<I: Identifiable, S: DexStorage<I>> Dex<I, S>
+where
+    I::Id: Eq + Hash,
+{
+    /// Get the values whose names start with a prefix, sorted by name.
+    /// Names are matched the same way as [Dex::try_get_named].
+    pub fn autocomplete(&self, prefix: impl AsRef<str>) -> Vec<&Arc<I>> {
+        let prefix = normalize_name(prefix.as_ref());
+        let mut values = self
+            .named()
+            .filter(|(name, _)| name.starts_with(&prefix))
+            .map(|(_, value)| value)
+            .collect::<Vec<_>>();
+        values.sort_by(|a, b| a.name().cmp(b.name()));
+        values
+    }
+
+    /// Search the names of this Dex, returning up to `limit` matches with the best first.
+    ///
+    /// Names are scored by edit distance, with a bonus for names that
+    /// start with or contain the letters of the search in order.
+    /// Searches without any letters or numbers never match.
+    pub fn search(&self, query: impl AsRef<str>, limit: usize) -> Vec<SearchMatch<'_, I>> {
+        let query = normalize_name(query.as_ref());
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let mut matches = self
+            .named()
+            .map(|(name, value)| SearchMatch {
+                value,
+                score: score(&query, name),
+            })
+            .filter(|m| m.score != 0)
+            .collect::<Vec<_>>();
+        matches.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| a.value.name().cmp(b.value.name()))
+        });
+        matches.truncate(limit);
+        matches
+    }
+
+    /// Get the value a misspelled name most likely meant,
+    /// if any name matches it well enough (see [SearchMatch::SUGGEST]).
+    pub fn suggest(&self, query: impl AsRef<str>) -> Option<&Arc<I>> {
+        self.search(query, 1)
+            .into_iter()
+            .find(|m| m.score >= SearchMatch::<I>::SUGGEST)
+            .map(|m| m.value)
+    }
+
+    /// Iterate over the values in the Dex with their normalized names, from the name index.
+    fn named(&self) -> impl Iterator<Item = (&str, &Arc<I>)> + '_ {
+        self.names.iter().flat_map(move |(name, ids)| {
+            ids.iter()
+                .filter_map(move |id| self.try_get(id))
+                .map(move |value| (name.as_str(), value))
+        })
+    }
+}
+
+/// Score how well a normalized name matches a normalized search.
+fn score(query: &str, name: &str) -> u8 {
+    if query == name {
+        return SearchMatch::<()>::EXACT;
+    }
+
+    let query = query.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+
+    if query.is_empty() || name.is_empty() {
+        return 0;
+    }
+
+    let longest = query.len().max(name.len());
+    let similarity = (longest - distance(&query, &name)) * 99 / longest;
+
+    let ratio = query.len().min(name.len()) * 19 / name.len();
+    let bonus = if name.starts_with(&query) {
+        80 + ratio
+    } else if is_subsequence(&query, &name) {
+        50 + ratio
+    } else {
+        0
+    };
+
+    similarity.max(bonus) as u8
+}
+
+/// The Levenshtein distance between two strings.
+fn distance(a: &[char], b: &[char]) -> usize {
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    let mut current = alloc::vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + (ca != cb) as usize;
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        core::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Check if every character of `query` appears in `name` in order.
+fn is_subsequence(query: &[char], name: &[char]) -> bool {
+    let mut name = name.iter();
+    query.iter().all(|c| name.any(|n| n == c))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use alloc::vec::Vec;
+
+    use crate::{dex::tests::test_move, moves::Move, Dex};
+
+    use super::SearchMatch;
+
+    #[test]
+    fn search() {
+        let dex = [
+            ("charmander", "Charmander"),
+            ("charmeleon", "Charmeleon"),
+            ("charizard", "Charizard"),
+            ("chansey", "Chansey"),
+            ("squirtle", "Squirtle"),
+        ]
+        .into_iter()
+        .map(|(id, name)| test_move(id, name))
+        .collect::<Dex<Move>>();
+
+        let names = dex
+            .autocomplete("char")
+            .into_iter()
+            .map(|m| m.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["Charizard", "Charmander", "Charmeleon"]);
+        assert!(dex.autocomplete("pika").is_empty());
+
+        assert_eq!(dex.suggest("charmandr").unwrap().name, "Charmander");
+        assert_eq!(dex.suggest("sqirtle").unwrap().name, "Squirtle");
+        assert_eq!(dex.suggest("chmdr").unwrap().name, "Charmander");
+        assert!(dex.suggest("bulbasaur").is_none());
+
+        let results = dex.search("Charmander", 2);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].value.name, "Charmander");
+        assert_eq!(results[0].score, SearchMatch::<Move>::EXACT);
+        assert!(results[1].score < results[0].score);
+    }
+
+    #[test]
+    fn empty_names() {
+        let dex = [("question", "???"), ("tackle", "Tackle")]
+            .into_iter()
+            .map(|(id, name)| test_move(id, name))
+            .collect::<Dex<Move>>();
+
+        assert!(dex.try_get_named("!!!").is_none());
+        assert!(dex.suggest("!!!").is_none());
+        assert!(dex.search("", 5).is_empty());
+        assert!(dex.search("!!!", 5).is_empty());
+
+        let names = dex
+            .autocomplete("")
+            .into_iter()
+            .map(|m| m.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["Tackle"]);
+    }
+}
@@ -0,0 +1,186 @@
+//! Ways a [Dex](crate::Dex) can store its values.
+
+use alloc::{sync::Arc, vec::Vec};
+use core::hash::Hash;
+
+use hashbrown::HashMap;
+
+use crate::{DenseId, Identifiable};
+
+/// How a [Dex](crate::Dex) keeps its values.
+pub trait DexStorage<I: Identifiable>: Default {
+    type Values<'a>: Iterator<Item = &'a Arc<I>>
+    where
+        Self: 'a,
+        I: 'a;
+
+    /// Get the value with an identifier.
+    fn get(&self, id: &I::Id) -> Option<&Arc<I>>;
+
+    /// Add a value, returning the value it replaced.
+    fn insert(&mut self, value: Arc<I>) -> Option<Arc<I>>;
+
+    /// Remove the value with an identifier.
+    fn remove(&mut self, id: &I::Id) -> Option<Arc<I>>;
+
+    /// Get the number of values.
+    fn len(&self) -> usize;
+
+    /// Check if there are no values.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterate over every value.
+    fn values(&self) -> Self::Values<'_>;
+
+    /// Take every value out of the storage.
+    fn into_values(self) -> Vec<Arc<I>>;
+}
+
+/// Stores values in a hash map, which works for any identifier.
+/// This is the default storage of a [Dex](crate::Dex).
+#[derive(Debug, Clone)]
+pub struct HashStorage<I: Identifiable>(HashMap<I::Id, Arc<I>>);
+
+impl<I: Identifiable> Default for HashStorage<I> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<I: Identifiable> DexStorage<I> for HashStorage<I>
+where
+    I::Id: Hash + Eq + Clone,
+{
+    type Values<'a> = hashbrown::hash_map::Values<'a, I::Id, Arc<I>> where I: 'a;
+
+    fn get(&self, id: &I::Id) -> Option<&Arc<I>> {
+        self.0.get(id)
+    }
+
+    fn insert(&mut self, value: Arc<I>) -> Option<Arc<I>> {
+        self.0.insert(value.id().clone(), value)
+    }
+
+    fn remove(&mut self, id: &I::Id) -> Option<Arc<I>> {
+        self.0.remove(id)
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn values(&self) -> Self::Values<'_> {
+        self.0.values()
+    }
+
+    fn into_values(self) -> Vec<Arc<I>> {
+        self.0.into_values().collect()
+    }
+}
+
+/// Stores values in an array indexed by their identifier (see [DenseId]).
+///
+/// Lookups do not need to hash, and values are iterated in order of their identifier.
+/// This works best when identifiers are close together, like pokedex numbers.
+#[derive(Debug, Clone)]
+pub struct DenseStorage<I> {
+    values: Vec<Option<Arc<I>>>,
+    len: usize,
+}
+
+impl<I> Default for DenseStorage<I> {
+    fn default() -> Self {
+        Self {
+            values: Default::default(),
+            len: 0,
+        }
+    }
+}
+
+impl<I: Identifiable> DexStorage<I> for DenseStorage<I>
+where
+    I::Id: DenseId,
+{
+    type Values<'a> = core::iter::Flatten<core::slice::Iter<'a, Option<Arc<I>>>> where I: 'a;
+
+    fn get(&self, id: &I::Id) -> Option<&Arc<I>> {
+        self.values.get(id.index()).and_then(Option::as_ref)
+    }
+
+    fn insert(&mut self, value: Arc<I>) -> Option<Arc<I>> {
+        let index = value.id().index();
+        if index >= self.values.len() {
+            self.values.resize(index + 1, None);
+        }
+        let previous = self.values[index].replace(value);
+        if previous.is_none() {
+            self.len += 1;
+        }
+        previous
+    }
+
+    fn remove(&mut self, id: &I::Id) -> Option<Arc<I>> {
+        let removed = self.values.get_mut(id.index())?.take()?;
+        self.len -= 1;
+        while let Some(None) = self.values.last() {
+            self.values.pop();
+        }
+        Some(removed)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn values(&self) -> Self::Values<'_> {
+        self.values.iter().flatten()
+    }
+
+    fn into_values(self) -> Vec<Arc<I>> {
+        self.values.into_iter().flatten().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use alloc::{vec, vec::Vec};
+
+    use crate::{
+        pokemon::{tests::test_pokemon, Pokemon, PokemonId},
+        DenseDex,
+    };
+
+    use super::{DenseStorage, HashStorage};
+
+    #[test]
+    fn dense_dex() {
+        let pokemon = |id| Pokemon {
+            id: PokemonId(id),
+            ..test_pokemon(vec![])
+        };
+
+        let mut pokedex = [pokemon(3), pokemon(0), pokemon(1)]
+            .into_iter()
+            .collect::<DenseDex<Pokemon>>();
+
+        assert_eq!(pokedex.len(), 3);
+        assert_eq!(
+            pokedex.ids().copied().collect::<Vec<_>>(),
+            [PokemonId(0), PokemonId(1), PokemonId(3)]
+        );
+        assert!(!pokedex.contains(&PokemonId(2)));
+        assert_eq!(pokedex.get(&PokemonId(2)).id, PokemonId(0));
+        assert_eq!(pokedex.get(&PokemonId(400)).id, PokemonId(0));
+
+        assert!(pokedex.remove(&PokemonId(3)).is_some());
+        assert!(pokedex.remove(&PokemonId(3)).is_none());
+        assert_eq!(pokedex.len(), 2);
+
+        let pokedex = pokedex.into_storage::<HashStorage<Pokemon>>();
+        assert!(pokedex.contains(&PokemonId(1)));
+        assert_eq!(pokedex.into_storage::<DenseStorage<Pokemon>>().len(), 2);
+    }
+}
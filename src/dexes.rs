@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    item::Item,
+    moves::Move,
+    pokemon::Pokemon,
+    validate::{validate, DexReport},
+    Dex, DexStorage, HashStorage,
+};
+
+/// Every [Dex] the game uses, kept together so they can be loaded, saved and validated as one.
+///
+/// Each Dex can use its own [DexStorage], which is a hash map by default.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(bound(
+    serialize = "P: DexStorage<Pokemon>, M: DexStorage<Move>, T: DexStorage<Item>",
+    deserialize = "P: DexStorage<Pokemon>, M: DexStorage<Move>, T: DexStorage<Item>"
+))]
+pub struct DexSet<
+    P = HashStorage<Pokemon>,
+    M = HashStorage<Move>,
+    T = HashStorage<Item>,
+> {
+    #[serde(default)]
+    pub pokedex: Dex<Pokemon, P>,
+    #[serde(default)]
+    pub movedex: Dex<Move, M>,
+    #[serde(default)]
+    pub itemdex: Dex<Item, T>,
+}
+
+impl<P: DexStorage<Pokemon>, M: DexStorage<Move>, T: DexStorage<Item>> DexSet<P, M, T> {
+    /// Check that every reference between the dexes points to an entry that exists (see [validate]).
+    pub fn validate(&self) -> DexReport {
+        validate(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::DexSet;
+
+    #[test]
+    fn fields() {
+        let dexes = serde_json::from_str::<DexSet>(r#"{"movedex":[]}"#).unwrap();
+        assert!(dexes.pokedex.is_empty());
+
+        let error = serde_json::from_str::<DexSet>(r#"{"pokedx":[]}"#).unwrap_err();
+        assert!(error.to_string().contains("pokedx"));
+    }
+}
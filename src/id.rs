@@ -15,3 +15,9 @@ pub trait Identifiable {
 
     fn name(&self) -> &str;
 }
+
+/// An identifier that is a small number, so values can be kept in an array (see [DenseDex](crate::DenseDex)).
+pub trait DenseId {
+    /// Get the position of this identifier in an array.
+    fn index(&self) -> usize;
+}
@@ -60,8 +60,9 @@ impl Identifiable for Item {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 pub enum ItemCategory {
+    #[default]
     Items,
     KeyItems,
     Pokeballs,
@@ -110,9 +111,3 @@ impl Default for Stackable {
         Self::Stackable(999)
     }
 }
-
-impl Default for ItemCategory {
-    fn default() -> Self {
-        Self::Items
-    }
-}
@@ -1,12 +1,12 @@
 use core::{ops::Deref};
 
-use alloc::sync::Arc;
+use alloc::{sync::Arc, vec::Vec};
 use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     item::{Item, ItemId, ItemStack},
-    Dex,
+    Dex, DexStorage,
 };
 
 use super::UserItemStack;
@@ -25,7 +25,7 @@ impl UserBag {
         match self.0.get_mut(&stack.item.id) {
             Some(bag_stack) => *bag_stack += stack.count,
             None => {
-                self.0.insert(stack.item.id.clone(), stack);
+                self.0.insert(stack.item.id, stack);
             }
         }
     }
@@ -82,19 +82,32 @@ impl UserBag {
 }
 
 impl BagData {
-    pub fn init(&self, dex: &Dex<Item>) -> Option<UserBag> {
-        Some(Bag(self
-            .0
-            .iter()
-            .flat_map(|(i, stack)| stack.init(dex).map(|stack| (i.clone(), stack)))
-            .collect()))
+    /// Initialize this bag. Fails with every item that is not in the Dex, sorted by id.
+    pub fn init(&self, dex: &Dex<Item, impl DexStorage<Item>>) -> Result<UserBag, Vec<ItemId>> {
+        let mut bag = HashMap::with_capacity(self.0.len());
+        let mut missing = Vec::new();
+        for (id, stack) in self.0.iter() {
+            match stack.init(dex) {
+                Some(stack) => {
+                    bag.insert(*id, stack);
+                }
+                None => missing.push(*id),
+            }
+        }
+        match missing.is_empty() {
+            true => Ok(Bag(bag)),
+            false => {
+                missing.sort();
+                Err(missing)
+            }
+        }
     }
 }
 
 impl<I: Deref<Target = Item>> Bag<I> {
 
     pub fn data(&self) -> BagData {
-        Bag(self.0.iter().map(|(id, stack)| (id.clone(), stack.data())).collect())
+        Bag(self.0.iter().map(|(id, stack)| (*id, stack.data())).collect())
     }
 
 }
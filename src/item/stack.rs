@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     item::{Item, ItemId, Stackable},
-    Dex,
+    Dex, DexStorage,
 };
 
 pub type StackSize = usize;
@@ -96,7 +96,7 @@ impl<I> AddAssign<StackSize> for ItemStack<I> {
 }
 
 impl ItemStackData {
-    pub fn init(&self, dex: &Dex<Item>) -> Option<UserItemStack> {
+    pub fn init(&self, dex: &Dex<Item, impl DexStorage<Item>>) -> Option<UserItemStack> {
         Some(ItemStack {
             item: dex.try_get(&self.item)?.clone(),
             count: self.count,
@@ -11,6 +11,9 @@ extern crate alloc;
 mod dex;
 pub use dex::*;
 
+mod dexes;
+pub use dexes::*;
+
 mod id;
 pub use id::*;
 
@@ -20,5 +23,6 @@ pub mod moves;
 pub mod pokemon;
 pub mod types;
 pub mod trainer;
+pub mod validate;
 
 pub type Money = u32;
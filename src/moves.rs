@@ -6,13 +6,15 @@
 use alloc::string::String;
 use serde::{Deserialize, Serialize};
 
-use crate::{Identifiable, UNKNOWN_ID};
+use crate::{types::PokemonType, Identifiable, Query, UNKNOWN_ID};
 
 pub mod owned;
 pub mod set;
 
 /// How many times a [Move] can be used before needing to be restored.
 pub type PP = u8;
+/// The base power of a [Move].
+pub type Power = u8;
 
 type IdInner = tinystr::TinyAsciiStr<16>;
 
@@ -30,9 +32,34 @@ pub struct Move {
 
     pub name: String,
 
+    /// Moves without a category are [MoveCategory::Status].
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub category: MoveCategory,
+
+    /// Moves without a type are [PokemonType::Unknown].
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub pokemon_type: PokemonType,
+
+    /// The base power of this move, or [None] if it does not deal damage directly.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power: Option<Power>,
+
     pub pp: PP,
 }
 
+/// Whether a [Move] deals physical or special damage, or has no damage at all.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum MoveCategory {
+    Physical,
+    Special,
+    #[default]
+    Status,
+}
+
+fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
 // impl Identifier<Move> for MoveId {
 //     fn as_id(&self) -> &<Move as Identifiable>::Id {
 //         self
@@ -53,6 +80,30 @@ impl Identifiable for Move {
     }
 }
 
+impl<'a> Query<'a, Move> {
+    /// Only match moves of a type.
+    /// Moves saved without a type only match [PokemonType::Unknown].
+    pub fn pokemon_type(self, pokemon_type: PokemonType) -> Self {
+        self.filter(move |m| m.pokemon_type == pokemon_type)
+    }
+
+    /// Only match moves of a category.
+    /// Moves saved without a category only match [MoveCategory::Status].
+    pub fn category(self, category: MoveCategory) -> Self {
+        self.filter(move |m| m.category == category)
+    }
+
+    /// Only match moves with at least a certain power.
+    pub fn min_power(self, power: Power) -> Self {
+        self.filter(move |m| m.power.map(|p| p >= power).unwrap_or_default())
+    }
+
+    /// Only match moves with at most a certain power.
+    pub fn max_power(self, power: Power) -> Self {
+        self.filter(move |m| m.power.map(|p| p <= power).unwrap_or_default())
+    }
+}
+
 impl Default for MoveId {
     fn default() -> Self {
         Move::UNKNOWN
@@ -72,3 +123,22 @@ impl core::str::FromStr for MoveId {
         s.parse().map(Self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use crate::Dex;
+
+    use super::{Move, MoveCategory};
+
+    #[test]
+    fn defaults() {
+        let data = r#"[{"id":"tackle","name":"Tackle","pp":35}]"#;
+        let dex = serde_json::from_str::<Dex<Move>>(data).unwrap();
+        assert_eq!(serde_json::to_string(&dex).unwrap(), data);
+
+        let tackle = dex.values().next().unwrap();
+        assert_eq!(tackle.category, MoveCategory::Status);
+        assert_eq!(tackle.power, None);
+    }
+}
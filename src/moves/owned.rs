@@ -3,7 +3,7 @@ use alloc::sync::Arc;
 
 use crate::{
     moves::{Move, MoveId, PP},
-    Dex, Identifiable,
+    Dex, DexStorage, Identifiable,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -38,7 +38,7 @@ impl UserMoveData {
 
     pub fn init(
         self,
-        dex: &Dex<Move>,
+        dex: &Dex<Move, impl DexStorage<Move>>,
     ) -> Option<UserMove> {
         dex.try_get(&self.id).cloned().map(UserMove::from)
     }
@@ -65,7 +65,7 @@ impl UserMove {
 
     pub fn data(&self) -> UserMoveData {
         UserMoveData {
-            id: self.m.id().clone(), 
+            id: *self.m.id(),
             pp: Some(self.pp)
         }
     }
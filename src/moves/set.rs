@@ -7,7 +7,7 @@ use crate::{
         owned::*,
         Move,
     },
-    Dex,
+    Dex, DexStorage,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,10 +15,13 @@ use crate::{
 pub struct MoveSet<M>(Vec<M>, usize);
 
 #[deprecated]
+#[allow(deprecated)]
 pub type MoveSetData = MoveSet<UserMoveData>;
 #[deprecated]
+#[allow(deprecated)]
 pub type UserMoveSet = MoveSet<UserMove>;
 
+#[allow(deprecated)]
 impl<M> MoveSet<M> {
     pub const DEFAULT_SIZE: usize = 4;
 
@@ -38,11 +41,11 @@ impl<M> MoveSet<M> {
         self.0.get_mut(index)
     }
 
-    pub fn iter(&self) -> core::slice::Iter<M> {
+    pub fn iter(&self) -> core::slice::Iter<'_, M> {
         self.0.iter()
     }
 
-    pub fn iter_mut(&mut self) -> core::slice::IterMut<M> {
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, M> {
         self.0.iter_mut()
     }
 
@@ -51,14 +54,16 @@ impl<M> MoveSet<M> {
     }
 }
 
+#[allow(deprecated)]
 impl<M> Default for MoveSet<M> {
     fn default() -> Self {
         Self(Default::default(), Self::DEFAULT_SIZE)
     }
 }
 
+#[allow(deprecated)]
 impl MoveSetData {
-    pub fn init(&self, dex: &Dex<Move>) -> Result<UserMoveSet, usize> {
+    pub fn init(&self, dex: &Dex<Move, impl DexStorage<Move>>) -> Result<UserMoveSet, usize> {
         Ok(MoveSet(
             {
                 let mut moves = Vec::new();
@@ -75,12 +80,14 @@ impl MoveSetData {
     }
 }
 
+#[allow(deprecated)]
 impl UserMoveSet {
     pub fn data(&self) -> MoveSetData {
         MoveSet(self.0.iter().map(UserMove::data).collect(), self.1)
     }
 }
 
+#[allow(deprecated)]
 impl UserMoveSet {
     pub fn is_full(&self) -> bool {
         self.0.len() >= self.1
@@ -105,6 +112,7 @@ impl UserMoveSet {
     }
 }
 
+#[allow(deprecated)]
 impl<M> Index<usize> for MoveSet<M> {
     type Output = M;
 
@@ -113,6 +121,7 @@ impl<M> Index<usize> for MoveSet<M> {
     }
 }
 
+#[allow(deprecated)]
 impl<M> IndexMut<usize> for MoveSet<M> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
         &mut self.0[index]
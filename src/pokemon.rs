@@ -1,16 +1,21 @@
 //! Types and structs related to Pokemon
 //!
 
-use alloc::{string::String, vec::Vec};
+use alloc::{string::String, sync::Arc, vec::Vec};
 use core::{
     fmt::{Display, Formatter, Result as FmtResult},
-    ops::RangeBounds,
+    ops::{RangeBounds, RangeInclusive},
 };
 
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
-use crate::{moves::MoveId, Identifiable, types::PokemonTypes};
+use crate::{
+    moves::MoveId,
+    trainer::TrainerId,
+    types::{PokemonType, PokemonTypes},
+    DenseId, Dex, DexStorage, Identifiable, Query,
+};
 
 pub mod owned;
 
@@ -19,11 +24,23 @@ pub mod party;
 pub mod data;
 use self::data::*;
 
+pub mod learnset;
+use self::learnset::{LearnMethod, Learnset};
+
+pub mod personality;
+use self::personality::{Personality, PersonalityAttributes};
+
 pub mod stat;
-use self::stat::{BaseStat, Stat, StatType, Stats};
+use self::stat::{
+    retro::{Dv, Dvs, RetroStatType, StatExp, StatExperience},
+    BaseStat, HyperTraining, Stat, StatSet, StatType, Stats,
+};
 
 mod nature;
 pub use self::nature::*;
+
+mod characteristic;
+pub use self::characteristic::*;
 /// The level of a pokemon. Usually 1 - 100.
 /// Levels determine a Pokemon's power, and higher is better.
 pub type Level = u8;
@@ -54,6 +71,8 @@ pub struct Pokemon {
     pub types: PokemonTypes,
 
     pub moves: Vec<LearnableMove>,
+    #[serde(default, skip_serializing_if = "Learnset::is_empty")]
+    pub learnset: Learnset,
     pub base: Stats,
 
     pub species: String,
@@ -82,35 +101,40 @@ impl Pokemon {
             .unwrap_or(Gender::None)
     }
 
+    /// Generate a random [Personality] and the attributes that come from it.
+    /// Unlike generating a gender and nature separately, these attributes are consistent with each other.
+    pub fn generate_personality(
+        &self,
+        random: &mut impl Rng,
+        trainer: Option<&TrainerId>,
+    ) -> PersonalityAttributes {
+        Personality::random(random).attributes(self, trainer)
+    }
+
+    /// Generate a random [Personality] whose attributes match a given [Gender], [Nature] and ability slot,
+    /// rerolling it like the games do when these are decided beforehand.
+    /// Returns [None] if no matching personality value was found, such as for a gender this pokemon cannot have.
+    pub fn generate_matching_personality(
+        &self,
+        random: &mut impl Rng,
+        trainer: Option<&TrainerId>,
+        gender: Option<Gender>,
+        nature: Option<Nature>,
+        ability_slot: Option<u8>,
+    ) -> Option<PersonalityAttributes> {
+        const ATTEMPTS: usize = 1 << 12;
+        (0..ATTEMPTS)
+            .map(|_| self.generate_personality(random, trainer))
+            .find(|attributes| {
+                gender.map(|g| g == attributes.gender).unwrap_or(true)
+                    && nature.map(|n| n == attributes.nature).unwrap_or(true)
+                    && ability_slot.map(|a| a == attributes.ability_slot).unwrap_or(true)
+            })
+    }
+
+    /// Generate a random [Nature].
     pub fn generate_nature(random: &mut impl Rng) -> Nature {
-        match random.gen_range(0..Nature::COUNT) {
-            0 => Nature::Adamant,
-            1 => Nature::Bashful,
-            2 => Nature::Bold,
-            3 => Nature::Brave,
-            4 => Nature::Calm,
-            5 => Nature::Careful,
-            6 => Nature::Docile,
-            7 => Nature::Gentle,
-            8 => Nature::Hardy,
-            9 => Nature::Hasty,
-            10 => Nature::Impish,
-            11 => Nature::Jolly,
-            12 => Nature::Lax,
-            13 => Nature::Lonely,
-            14 => Nature::Mild,
-            15 => Nature::Modest,
-            16 => Nature::Naive,
-            17 => Nature::Naughty,
-            18 => Nature::Quiet,
-            19 => Nature::Quirky,
-            20 => Nature::Rash,
-            21 => Nature::Relaxed,
-            22 => Nature::Sassy,
-            23 => Nature::Serious,
-            24 => Nature::Timid,
-            _ => unreachable!(),
-        }
+        Nature::ALL[random.gen_range(0..Nature::COUNT)]
     }
 
     /// Get the amount of [Experience] that can be gained from defeating this pokemon at a certain [Level].
@@ -134,56 +158,146 @@ impl Pokemon {
             .map(|m| &m.1)
     }
 
+    /// Get every way this pokemon can learn a move.
+    pub fn learn_methods<'s>(
+        &'s self,
+        id: &'s MoveId,
+    ) -> impl Iterator<Item = LearnMethod> + 's {
+        self.moves
+            .iter()
+            .filter(move |m| &m.1 == id)
+            .map(|m| LearnMethod::Level(m.0))
+            .chain(self.learnset.methods(id))
+    }
+
+    /// Check if this pokemon can learn a move in any way.
+    pub fn can_learn(&self, id: &MoveId) -> bool {
+        self.learn_methods(id).next().is_some()
+    }
+
+    /// Check if this pokemon can learn a move by the time it reaches a [Level].
+    /// Level up moves above the level are not counted.
+    pub fn can_learn_at(&self, id: &MoveId, level: Level) -> bool {
+        self.learn_methods(id).any(|method| match method {
+            LearnMethod::Level(l) => l <= level,
+            _ => true,
+        })
+    }
+
     /// Get the value of a [BaseStat] from basic stats.
+    /// Hyper trained stats use [Stats::MAX_IV] instead of their IV.
     pub fn stat(
         &self,
         ivs: &Stats,
+        training: &HyperTraining,
         evs: &Stats,
         level: Level,
         nature: Nature,
         stat: StatType,
     ) -> BaseStat {
+        let iv = match training[stat] {
+            true => Stats::MAX_IV,
+            false => ivs[stat],
+        };
         match stat {
             StatType::Health => Self::base_hp(
                 self.base[StatType::Health],
-                ivs[StatType::Health],
+                iv,
                 evs[StatType::Health],
                 level,
             ),
             stat => Self::base_stat(
                 self.base[stat],
-                ivs[stat],
+                iv,
                 evs[stat],
                 level,
-                nature.multiplier(&stat) as _,
+                nature.percent(&stat),
             ),
         }
     }
 
+    /// Get every [BaseStat] from basic stats.
+    /// Hyper trained stats use [Stats::MAX_IV] instead of their IV.
+    pub fn stats(
+        &self,
+        ivs: &Stats,
+        training: &HyperTraining,
+        evs: &Stats,
+        level: Level,
+        nature: Nature,
+    ) -> StatSet<StatType, BaseStat> {
+        StatSet::from_fn(|stat| self.stat(ivs, training, evs, level, nature, stat))
+    }
+
+    /// Get the range of IVs that give a [BaseStat] its value.
+    /// Returns [None] if no IV gives the value.
+    pub fn iv_range(
+        &self,
+        evs: &Stats,
+        level: Level,
+        nature: Nature,
+        stat: StatType,
+        value: BaseStat,
+    ) -> Option<RangeInclusive<Stat>> {
+        let mut ivs = Stats::default();
+        let mut matches = Stats::IV_RANGE.filter(|iv| {
+            ivs[stat] = *iv;
+            self.stat(&ivs, &HyperTraining::default(), evs, level, nature, stat) == value
+        });
+        let start = matches.next()?;
+        Some(start..=matches.last().unwrap_or(start))
+    }
+
     /// Get the value of a [BaseStat] from basic stats, excluding health.
-    pub fn base_stat(base: Stat, iv: Stat, ev: Stat, level: Level, multiplier: f64) -> BaseStat {
-        let (.., mut base) = Self::base(base, iv, ev, level);
-        base += 5.0;
-        base *= multiplier;
-        base as _
+    /// The nature percent is 90, 100 or 110 (see [Nature::percent]).
+    pub fn base_stat(base: Stat, iv: Stat, ev: Stat, level: Level, percent: u8) -> BaseStat {
+        let base = Self::base(base, iv, ev, level) + 5;
+        (base * percent as u32 / 100) as _
     }
 
     /// Get the base [Health] of a pokemon from basic stats.
+    /// Pokemon with a base HP of 1 (Shedinja) always have 1 HP.
     pub fn base_hp(base: Stat, iv: Stat, ev: Stat, level: Level) -> Health {
-        let (level, mut base) = Self::base(base, iv, ev, level);
-        base += level;
-        base += 10.0;
-        base as _
+        match base {
+            1 => 1,
+            base => (Self::base(base, iv, ev, level) + level as u32 + 10) as _,
+        }
+    }
+
+    /// Get the value of a [BaseStat] using the first and second generation formulas.
+    /// Special Attack and Special Defense share a DV and stat experience, but keep their own base stats.
+    pub fn retro_stat(
+        &self,
+        dvs: &Dvs,
+        exp: &StatExperience,
+        level: Level,
+        stat: StatType,
+    ) -> BaseStat {
+        let retro = RetroStatType::from_modern(stat);
+        let (dv, exp) = (dvs.get(retro), exp[retro]);
+        match stat {
+            StatType::Health => Self::retro_base_hp(self.base[stat], dv, exp, level),
+            stat => Self::retro_base_stat(self.base[stat], dv, exp, level),
+        }
     }
 
-    /// returns level and partially completed base stat
-    fn base(base: Stat, iv: Stat, ev: Stat, level: Level) -> (f64, f64) {
-        let level = level as f64;
-        let mut base = 2.0 * base as f64 + iv as f64;
-        base += ev as f64 / 4.0;
-        base *= level;
-        base /= 100.0;
-        (level, base)
+    /// Get the value of a [BaseStat] from basic retro stats, excluding health.
+    pub fn retro_base_stat(base: Stat, dv: Dv, exp: StatExp, level: Level) -> BaseStat {
+        (Self::retro_base(base, dv, exp, level) + 5) as _
+    }
+
+    /// Get the base [Health] of a pokemon from basic retro stats.
+    pub fn retro_base_hp(base: Stat, dv: Dv, exp: StatExp, level: Level) -> Health {
+        (Self::retro_base(base, dv, exp, level) + level as u32 + 10) as _
+    }
+
+    fn retro_base(base: Stat, dv: Dv, exp: StatExp, level: Level) -> u32 {
+        ((base as u32 + dv as u32) * 2 + StatExperience::bonus(exp)) * level as u32 / 100
+    }
+
+    /// returns the partially completed base stat, rounded down at each step
+    fn base(base: Stat, iv: Stat, ev: Stat, level: Level) -> u32 {
+        (2 * base as u32 + iv as u32 + ev as u32 / 4) * level as u32 / 100
     }
 
     /// The default [Friendship] of a pokemon.
@@ -212,6 +326,50 @@ impl Identifiable for Pokemon {
     }
 }
 
+impl<'a> Query<'a, Pokemon> {
+    /// Only match pokemon that have a type.
+    pub fn pokemon_type(self, pokemon_type: PokemonType) -> Self {
+        self.filter(move |p| {
+            p.types.primary == pokemon_type || p.types.secondary == Some(pokemon_type)
+        })
+    }
+
+    /// Only match pokemon with a base stat of at least a certain value.
+    pub fn min_base(self, stat: StatType, value: Stat) -> Self {
+        self.filter(move |p| p.base[stat] >= value)
+    }
+
+    /// Only match pokemon with a base stat of at most a certain value.
+    pub fn max_base(self, stat: StatType, value: Stat) -> Self {
+        self.filter(move |p| p.base[stat] <= value)
+    }
+
+    /// Only match pokemon that can learn a move in any way.
+    pub fn learns(self, id: MoveId) -> Self {
+        self.filter(move |p| p.can_learn(&id))
+    }
+}
+
+impl DenseId for PokemonId {
+    fn index(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl<S: DexStorage<Pokemon>> Dex<Pokemon, S> {
+    /// Get every way a pokemon can learn a move.
+    /// Returns [None] if the pokemon is not in the Dex.
+    pub fn learn_methods(&self, pokemon: &PokemonId, id: &MoveId) -> Option<Vec<LearnMethod>> {
+        self.try_get(pokemon)
+            .map(|pokemon| pokemon.learn_methods(id).collect())
+    }
+
+    /// Get every pokemon in the Dex that can learn a move, in order of their id.
+    pub fn learners<'d>(&'d self, id: &'d MoveId) -> impl Iterator<Item = &'d Arc<Pokemon>> + 'd {
+        self.values_sorted().filter(move |pokemon| pokemon.can_learn(id))
+    }
+}
+
 impl Display for Pokemon {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         write!(f, "#{} {}", self.id, self.name)
@@ -237,21 +395,22 @@ impl Display for PokemonId {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
 
-    use alloc::vec;
+    use alloc::{vec, vec::Vec};
 
     use crate::{
+        dex::tests::test_move,
         item::Item,
-        moves::{Move, PP, owned::UserMoveData, MoveId},
+        moves::{Move, owned::UserMoveData},
         pokemon::{
             data::{Breeding, LearnableMove, Training},
             owned::UserPokemonData,
-            stat::{StatSet, StatType},
-            Nature, Pokemon,
+            stat::{StatSet, StatType, Stats},
+            Gender, Level, Nature, Pokemon,
         },
         types::{PokemonType, PokemonTypes},
-        Dex,
+        Dex, DexSet,
     };
 
     #[test]
@@ -261,26 +420,48 @@ mod tests {
             15,
             50,
             50,
-            Nature::Adamant.multiplier(&StatType::Attack) as _,
+            Nature::Adamant.percent(&StatType::Attack),
         );
-        println!("{}", attack);
-        assert!(attack == 136);
+        assert_eq!(attack, 135);
+
+        // (base, iv, ev, level, nature, stat, expected)
+        let table = [
+            // Garchomp
+            (108, 24, 74, 78, Nature::Adamant, StatType::Health, 289),
+            (130, 12, 190, 78, Nature::Adamant, StatType::Attack, 278),
+            (95, 30, 91, 78, Nature::Adamant, StatType::Defense, 193),
+            (80, 16, 48, 78, Nature::Adamant, StatType::SpAttack, 135),
+            (85, 23, 84, 78, Nature::Adamant, StatType::SpDefense, 171),
+            (102, 5, 23, 78, Nature::Adamant, StatType::Speed, 171),
+            // Pikachu
+            (35, 31, 0, 50, Nature::Timid, StatType::Health, 110),
+            (90, 31, 252, 50, Nature::Timid, StatType::Speed, 156),
+            (55, 31, 0, 50, Nature::Timid, StatType::Attack, 67),
+            // Blissey
+            (255, 31, 252, 100, Nature::Bold, StatType::Health, 714),
+            // Shedinja
+            (1, 31, 252, 100, Nature::Adamant, StatType::Health, 1),
+        ];
+
+        for (base, iv, ev, level, nature, stat, expected) in table {
+            let actual = match stat {
+                StatType::Health => Pokemon::base_hp(base, iv, ev, level),
+                stat => Pokemon::base_stat(base, iv, ev, level, nature.percent(&stat)),
+            };
+            assert_eq!(actual, expected, "{:?} {:?}", stat, (base, iv, ev, level, nature));
+        }
     }
 
-    #[test]
-    fn dex() {
-        let mut pokedex = Dex::<Pokemon>::default();
-
-        let test = "test".parse().unwrap();
-
-        let v = Pokemon {
+    pub(crate) fn test_pokemon(moves: Vec<LearnableMove>) -> Pokemon {
+        Pokemon {
             id: Default::default(),
             name: "Test".to_owned(),
             types: PokemonTypes {
                 primary: PokemonType::Bug,
                 secondary: Some(PokemonType::Dragon),
             },
-            moves: vec![LearnableMove(1, test)],
+            moves,
+            learnset: Default::default(),
             base: StatSet::uniform(60),
             species: "Test Species".to_owned(),
             evolution: None,
@@ -291,50 +472,55 @@ mod tests {
                 growth: Default::default(),
             },
             breeding: Breeding { gender: None },
-        };
+        }
+    }
 
-        pokedex.insert(v);
+    pub(crate) fn test_user_pokemon(level: Level, moves: Vec<UserMoveData>) -> UserPokemonData {
+        UserPokemonData {
+            pokemon: Default::default(),
+            level,
+            gender: Some(Gender::None),
+            nature: Some(Nature::Hardy),
+            nature_override: None,
+            ability_slot: Some(0),
+            personality: None,
+            hp: None,
+            ivs: Some(Stats::default_iv()),
+            evs: Default::default(),
+            hyper_training: Default::default(),
+            friendship: Pokemon::default_friendship(),
+            ailment: None,
+            nickname: None,
+            moves,
+            item: Default::default(),
+            experience: Default::default(),
+        }
+    }
 
-        let mut movedex = Dex::<Move>::default();
+    #[test]
+    fn dex() {
+        let mut pokedex = Dex::<Pokemon>::default();
 
-        let v = Move {
-            id: test,
-            name: "Test Move".to_owned(),
-            // category: MoveCategory::Physical,
-            // pokemon_type: PokemonType::Bug,
-            // accuracy: None,
-            // power: Some(Power::MAX),
-            pp: PP::MAX,
-            // priority: 0,
-            // target: MoveTarget::Opponent,
-            // contact: false,
-            // crit_rate: 1,
-        };
+        let test = "test".parse().unwrap();
 
-        movedex.insert(v);
+        pokedex.insert(test_pokemon(vec![LearnableMove(1, test)]));
 
-        let itemdex = Dex::<Item>::default();
+        let mut movedex = Dex::<Move>::default();
 
-        let pokemon = UserPokemonData {
-            pokemon: Default::default(),
-            level: 30,
-            gender: crate::pokemon::data::Gender::None,
-            nature: todo!(),
-            hp: todo!(),
-            ivs: todo!(),
-            evs: todo!(),
-            friendship: todo!(),
-            ailment: todo!(),
-            nickname: todo!(),
-            moves: vec![UserMoveData::from(test)],
-            item: Default::default(),
-            experience: Default::default(),
+        movedex.insert(test_move("test", "Test Move"));
+
+        let dexes = DexSet {
+            pokedex,
+            movedex,
+            itemdex: Dex::<Item>::default(),
         };
 
+        let pokemon = test_user_pokemon(30, vec![UserMoveData::from(test)]);
+
         let mut rng = rand::rngs::mock::StepRng::new(12, 24);
 
         let pokemon = pokemon
-            .init(&pokedex, &movedex, &itemdex, Some(&mut rng))
+            .init(&dexes, Some(&mut rng))
             .unwrap();
 
         assert!(!pokemon.moves.is_empty())
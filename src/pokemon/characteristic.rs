@@ -0,0 +1,189 @@
+use core::fmt::{Display, Formatter, Result as FmtResult};
+
+use serde::{Deserialize, Serialize};
+
+use super::stat::{Stat, StatType, Stats};
+
+/// A hint at a pokemon's highest IV.
+/// The variant names can be used as keys for translated text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum Characteristic {
+    LovesToEat,
+    TakesPlentyOfSiestas,
+    NodsOffALot,
+    ScattersThingsOften,
+    LikesToRelax,
+
+    ProudOfItsPower,
+    LikesToThrashAbout,
+    ALittleQuickTempered,
+    LikesToFight,
+    QuickTempered,
+
+    SturdyBody,
+    CapableOfTakingHits,
+    HighlyPersistent,
+    GoodEndurance,
+    GoodPerseverance,
+
+    HighlyCurious,
+    Mischievous,
+    ThoroughlyCunning,
+    OftenLostInThought,
+    VeryFinicky,
+
+    StrongWilled,
+    SomewhatVain,
+    StronglyDefiant,
+    HatesToLose,
+    SomewhatStubborn,
+
+    LikesToRun,
+    AlertToSounds,
+    ImpetuousAndSilly,
+    SomewhatOfAClown,
+    QuickToFlee,
+}
+
+/// The order stats are checked in when IVs are tied.
+const ORDER: [StatType; 6] = [
+    StatType::Health,
+    StatType::Attack,
+    StatType::Defense,
+    StatType::Speed,
+    StatType::SpAttack,
+    StatType::SpDefense,
+];
+
+impl Characteristic {
+    /// Every characteristic, five for each [StatType].
+    pub const ALL: [Self; 30] = [
+        Self::LovesToEat,
+        Self::TakesPlentyOfSiestas,
+        Self::NodsOffALot,
+        Self::ScattersThingsOften,
+        Self::LikesToRelax,
+        Self::ProudOfItsPower,
+        Self::LikesToThrashAbout,
+        Self::ALittleQuickTempered,
+        Self::LikesToFight,
+        Self::QuickTempered,
+        Self::SturdyBody,
+        Self::CapableOfTakingHits,
+        Self::HighlyPersistent,
+        Self::GoodEndurance,
+        Self::GoodPerseverance,
+        Self::HighlyCurious,
+        Self::Mischievous,
+        Self::ThoroughlyCunning,
+        Self::OftenLostInThought,
+        Self::VeryFinicky,
+        Self::StrongWilled,
+        Self::SomewhatVain,
+        Self::StronglyDefiant,
+        Self::HatesToLose,
+        Self::SomewhatStubborn,
+        Self::LikesToRun,
+        Self::AlertToSounds,
+        Self::ImpetuousAndSilly,
+        Self::SomewhatOfAClown,
+        Self::QuickToFlee,
+    ];
+
+    /// Get the characteristic of a set of IVs.
+    /// Ties between the highest IVs are broken using the personality value.
+    pub fn new(ivs: &Stats, personality: u32) -> Self {
+        let start = (personality % 6) as usize;
+        let stat = (0..ORDER.len())
+            .map(|i| ORDER[(start + i) % ORDER.len()])
+            .reduce(|highest, stat| match ivs[stat] > ivs[highest] {
+                true => stat,
+                false => highest,
+            })
+            .unwrap_or(StatType::Health);
+        Self::from_stat(stat, ivs[stat])
+    }
+
+    /// Get the characteristic of a [StatType] being the highest IV.
+    pub fn from_stat(stat: StatType, iv: Stat) -> Self {
+        Self::ALL[stat as usize * 5 + (iv % 5) as usize]
+    }
+
+    /// Get the [StatType] with the highest IV.
+    pub fn stat(&self) -> StatType {
+        match *self as usize / 5 {
+            0 => StatType::Health,
+            1 => StatType::Attack,
+            2 => StatType::Defense,
+            3 => StatType::SpAttack,
+            4 => StatType::SpDefense,
+            _ => StatType::Speed,
+        }
+    }
+
+    /// The english text of this characteristic.
+    pub const fn text(&self) -> &'static str {
+        match self {
+            Self::LovesToEat => "Loves to eat",
+            Self::TakesPlentyOfSiestas => "Takes plenty of siestas",
+            Self::NodsOffALot => "Nods off a lot",
+            Self::ScattersThingsOften => "Scatters things often",
+            Self::LikesToRelax => "Likes to relax",
+            Self::ProudOfItsPower => "Proud of its power",
+            Self::LikesToThrashAbout => "Likes to thrash about",
+            Self::ALittleQuickTempered => "A little quick tempered",
+            Self::LikesToFight => "Likes to fight",
+            Self::QuickTempered => "Quick tempered",
+            Self::SturdyBody => "Sturdy body",
+            Self::CapableOfTakingHits => "Capable of taking hits",
+            Self::HighlyPersistent => "Highly persistent",
+            Self::GoodEndurance => "Good endurance",
+            Self::GoodPerseverance => "Good perseverance",
+            Self::HighlyCurious => "Highly curious",
+            Self::Mischievous => "Mischievous",
+            Self::ThoroughlyCunning => "Thoroughly cunning",
+            Self::OftenLostInThought => "Often lost in thought",
+            Self::VeryFinicky => "Very finicky",
+            Self::StrongWilled => "Strong willed",
+            Self::SomewhatVain => "Somewhat vain",
+            Self::StronglyDefiant => "Strongly defiant",
+            Self::HatesToLose => "Hates to lose",
+            Self::SomewhatStubborn => "Somewhat stubborn",
+            Self::LikesToRun => "Likes to run",
+            Self::AlertToSounds => "Alert to sounds",
+            Self::ImpetuousAndSilly => "Impetuous and silly",
+            Self::SomewhatOfAClown => "Somewhat of a clown",
+            Self::QuickToFlee => "Quick to flee",
+        }
+    }
+}
+
+impl Display for Characteristic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.text())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use alloc::string::ToString;
+
+    use crate::pokemon::stat::{StatType, Stats};
+
+    use super::Characteristic;
+
+    #[test]
+    fn characteristic() {
+        let mut ivs = Stats::uniform(20);
+        assert_eq!(Characteristic::new(&ivs, 0), Characteristic::LovesToEat);
+        assert_eq!(Characteristic::new(&ivs, 3), Characteristic::LikesToRun);
+        assert_eq!(Characteristic::new(&ivs, 5), Characteristic::StrongWilled);
+
+        ivs[StatType::Attack] = 31;
+        let characteristic = Characteristic::new(&ivs, 3);
+        assert_eq!(characteristic, Characteristic::LikesToThrashAbout);
+        assert_eq!(characteristic.stat(), StatType::Attack);
+        assert_eq!(characteristic.to_string(), "Likes to thrash about");
+    }
+}
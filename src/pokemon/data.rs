@@ -63,11 +63,12 @@ pub struct Breeding {
 }
 
 /// How fast a pokemon can level up.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum GrowthRate {
     Slow,
     Fast,
     Medium,
+    #[default]
     MediumSlow,
     FastThenVerySlow,
     SlowThenVeryFast,
@@ -82,12 +83,6 @@ pub enum PokemonTexture {
     Icon,
 }
 
-impl Default for GrowthRate {
-    fn default() -> Self {
-        Self::MediumSlow
-    }
-}
-
 impl GrowthRate {
     /// Get the amount of [Experience] needed to [Level] up.
     pub fn max_exp(self, level: Level) -> Experience {
@@ -102,8 +97,8 @@ impl GrowthRate {
                     (6 * level.pow(3)) / 5 - (15 * level.pow(2)) + (100 * level) - 140
                 }
                 _ => {
-                    (1.2 * level.pow(3) as f32) as i32 - 15 * level.pow(2) as i32
-                        + 100 * level as i32
+                    (1.2 * level.pow(3) as f32) as i32 - 15 * level.pow(2)
+                        + 100 * level
                         - 140
                 } // MediumSlow
             },
@@ -0,0 +1,114 @@
+//! Moves a Pokemon can learn outside of leveling up.
+
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{moves::MoveId, pokemon::Level};
+
+/// How a Pokemon can learn a move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LearnMethod {
+    /// Learned when reaching a certain [Level].
+    Level(Level),
+    /// Learned from a TM or HM.
+    Machine,
+    /// Taught by a move tutor.
+    Tutor,
+    /// Inherited from a parent when hatching.
+    Egg,
+    /// Only obtainable from an event.
+    Event,
+}
+
+/// The moves a Pokemon can learn that are not learned by leveling up.
+/// Level up moves are kept in [Pokemon::moves](crate::pokemon::Pokemon::moves).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Learnset {
+    /// Moves learned from TMs and HMs.
+    #[serde(default)]
+    pub machine: Vec<MoveId>,
+    /// Moves taught by move tutors.
+    #[serde(default)]
+    pub tutor: Vec<MoveId>,
+    /// Moves inherited through breeding.
+    #[serde(default)]
+    pub egg: Vec<MoveId>,
+    /// Moves only given out by events.
+    #[serde(default)]
+    pub event: Vec<MoveId>,
+}
+
+impl Learnset {
+    /// Get the ways a move can be learned from this learnset.
+    pub fn methods<'a>(&'a self, id: &'a MoveId) -> impl Iterator<Item = LearnMethod> + 'a {
+        [
+            (&self.machine, LearnMethod::Machine),
+            (&self.tutor, LearnMethod::Tutor),
+            (&self.egg, LearnMethod::Egg),
+            (&self.event, LearnMethod::Event),
+        ]
+        .into_iter()
+        .filter(move |(moves, ..)| moves.contains(id))
+        .map(|(.., method)| method)
+    }
+
+    /// Check if a move can be learned from a TM or HM.
+    pub fn machine(&self, id: &MoveId) -> bool {
+        self.machine.contains(id)
+    }
+
+    /// Check if this learnset has no moves.
+    pub fn is_empty(&self) -> bool {
+        self.machine.is_empty()
+            && self.tutor.is_empty()
+            && self.egg.is_empty()
+            && self.event.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use alloc::{vec, vec::Vec};
+
+    use crate::{
+        moves::MoveId,
+        pokemon::{data::LearnableMove, tests::test_pokemon, Pokemon},
+        Dex,
+    };
+
+    use super::{LearnMethod, Learnset};
+
+    #[test]
+    fn learnset() {
+        let tackle: MoveId = "tackle".parse().unwrap();
+        let surf: MoveId = "surf".parse().unwrap();
+        let ember: MoveId = "ember".parse().unwrap();
+
+        let mut pokemon = test_pokemon(vec![LearnableMove(1, tackle), LearnableMove(20, surf)]);
+        pokemon.learnset = Learnset {
+            machine: vec![surf],
+            egg: vec![tackle],
+            ..Default::default()
+        };
+
+        let methods = pokemon.learn_methods(&surf).collect::<Vec<_>>();
+        assert_eq!(methods, [LearnMethod::Level(20), LearnMethod::Machine]);
+        assert!(pokemon.can_learn_at(&surf, 5));
+        assert!(!pokemon.can_learn(&ember));
+
+        let mut pokedex = Dex::<Pokemon>::default();
+        pokedex.insert(pokemon);
+
+        assert_eq!(
+            pokedex.learn_methods(&Default::default(), &tackle),
+            Some(vec![LearnMethod::Level(1), LearnMethod::Egg])
+        );
+        assert_eq!(pokedex.learners(&ember).count(), 0);
+
+        let saved = serde_json::to_string(&test_pokemon(Vec::new())).unwrap();
+        assert!(!saved.contains("learnset"));
+    }
+}
@@ -2,107 +2,182 @@ use serde::{Deserialize, Serialize};
 
 use super::stat::StatType;
 
+/// Natures raise one stat and lower another.
+/// They are listed in the same order as the games,
+/// so a nature's index is its increased stat times five plus its decreased stat (see [Nature::STATS]).
+/// Natures that raise and lower the same stat have no effect.
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub enum Nature {
+    Hardy,
+    Lonely,
+    Brave,
     Adamant,
-    Bashful,
+    Naughty,
     Bold,
-    Brave,
-    Calm,
-    Careful,
     Docile,
-    Gentle,
-    Hardy,
-    Hasty,
+    Relaxed,
     Impish,
-    Jolly,
     Lax,
-    Lonely,
-    Mild,
-    Modest,
+    Timid,
+    Hasty,
+    Serious,
+    Jolly,
     Naive,
-    Naughty,
+    Modest,
+    Mild,
     Quiet,
-    Quirky,
+    Bashful,
     Rash,
-    Relaxed,
+    Calm,
+    Gentle,
     Sassy,
-    Serious,
-    Timid,
+    Careful,
+    Quirky,
+}
+
+/// The flavors of berries and other food, which pokemon like or dislike based on their [Nature].
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub enum Flavor {
+    Spicy,
+    Sour,
+    Sweet,
+    Dry,
+    Bitter,
 }
 
 impl Nature {
     pub const COUNT: usize = 25;
 
+    /// Every nature, in order of its index.
+    pub const ALL: [Self; Self::COUNT] = [
+        Self::Hardy,
+        Self::Lonely,
+        Self::Brave,
+        Self::Adamant,
+        Self::Naughty,
+        Self::Bold,
+        Self::Docile,
+        Self::Relaxed,
+        Self::Impish,
+        Self::Lax,
+        Self::Timid,
+        Self::Hasty,
+        Self::Serious,
+        Self::Jolly,
+        Self::Naive,
+        Self::Modest,
+        Self::Mild,
+        Self::Quiet,
+        Self::Bashful,
+        Self::Rash,
+        Self::Calm,
+        Self::Gentle,
+        Self::Sassy,
+        Self::Careful,
+        Self::Quirky,
+    ];
+
+    /// The stats natures change, in the order of the nature table.
+    pub const STATS: [StatType; 5] = [
+        StatType::Attack,
+        StatType::Defense,
+        StatType::Speed,
+        StatType::SpAttack,
+        StatType::SpDefense,
+    ];
+
+    /// The flavor each of [Nature::STATS] is linked to.
+    pub const FLAVORS: [Flavor; 5] = [
+        Flavor::Spicy,
+        Flavor::Sour,
+        Flavor::Sweet,
+        Flavor::Dry,
+        Flavor::Bitter,
+    ];
+
+    /// Get the index of this nature.
+    pub const fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// Get a nature from its index.
+    pub const fn from_index(index: usize) -> Option<Self> {
+        match index < Self::COUNT {
+            true => Some(Self::ALL[index]),
+            false => None,
+        }
+    }
+
+    /// Check if this nature does not change any stats.
+    pub const fn is_neutral(&self) -> bool {
+        self.index() / 5 == self.index() % 5
+    }
+
     pub const fn increases(&self) -> Option<StatType> {
-        match self {
-            Nature::Adamant => Some(StatType::Attack),
-            Nature::Bashful => None,
-            Nature::Bold => Some(StatType::Defense),
-            Nature::Brave => Some(StatType::Attack),
-            Nature::Calm => Some(StatType::SpDefense),
-            Nature::Careful => Some(StatType::SpDefense),
-            Nature::Docile => None,
-            Nature::Gentle => Some(StatType::SpDefense),
-            Nature::Hardy => None,
-            Nature::Hasty => Some(StatType::Speed),
-            Nature::Impish => Some(StatType::Defense),
-            Nature::Jolly => Some(StatType::Speed),
-            Nature::Lax => Some(StatType::Defense),
-            Nature::Lonely => Some(StatType::Attack),
-            Nature::Mild => Some(StatType::SpAttack),
-            Nature::Modest => Some(StatType::SpAttack),
-            Nature::Naive => Some(StatType::Speed),
-            Nature::Naughty => Some(StatType::Attack),
-            Nature::Quiet => Some(StatType::SpAttack),
-            Nature::Quirky => None,
-            Nature::Rash => Some(StatType::SpAttack),
-            Nature::Relaxed => Some(StatType::Defense),
-            Nature::Sassy => Some(StatType::SpDefense),
-            Nature::Serious => None,
-            Nature::Timid => Some(StatType::Speed),
+        match self.is_neutral() {
+            true => None,
+            false => Some(Self::STATS[self.index() / 5]),
         }
     }
 
     pub const fn decreases(&self) -> Option<StatType> {
-        match self {
-            Nature::Adamant => Some(StatType::SpAttack),
-            Nature::Bashful => None,
-            Nature::Bold => Some(StatType::Attack),
-            Nature::Brave => Some(StatType::Speed),
-            Nature::Calm => Some(StatType::Attack),
-            Nature::Careful => Some(StatType::SpAttack),
-            Nature::Docile => None,
-            Nature::Gentle => Some(StatType::Defense),
-            Nature::Hardy => None,
-            Nature::Hasty => Some(StatType::Defense),
-            Nature::Impish => Some(StatType::SpAttack),
-            Nature::Jolly => Some(StatType::SpAttack),
-            Nature::Lax => Some(StatType::SpDefense),
-            Nature::Lonely => Some(StatType::Defense),
-            Nature::Mild => Some(StatType::Defense),
-            Nature::Modest => Some(StatType::Attack),
-            Nature::Naive => Some(StatType::SpDefense),
-            Nature::Naughty => Some(StatType::SpDefense),
-            Nature::Quiet => Some(StatType::Speed),
-            Nature::Quirky => None,
-            Nature::Rash => Some(StatType::SpDefense),
-            Nature::Relaxed => Some(StatType::Speed),
-            Nature::Sassy => Some(StatType::Speed),
-            Nature::Serious => None,
-            Nature::Timid => Some(StatType::Speed),
+        match self.is_neutral() {
+            true => None,
+            false => Some(Self::STATS[self.index() % 5]),
+        }
+    }
+
+    /// The [Flavor] a pokemon with this nature likes.
+    pub const fn likes(&self) -> Option<Flavor> {
+        match self.is_neutral() {
+            true => None,
+            false => Some(Self::FLAVORS[self.index() / 5]),
+        }
+    }
+
+    /// The [Flavor] a pokemon with this nature dislikes.
+    pub const fn dislikes(&self) -> Option<Flavor> {
+        match self.is_neutral() {
+            true => None,
+            false => Some(Self::FLAVORS[self.index() % 5]),
+        }
+    }
+
+    /// Get the percentage a stat is multiplied by, which is 90, 100 or 110.
+    pub fn percent(&self, stat: &StatType) -> u8 {
+        match (self.increases(), self.decreases()) {
+            (Some(i), ..) if &i == stat => 110,
+            (.., Some(d)) if &d == stat => 90,
+            _ => 100,
         }
     }
 
     pub fn multiplier(&self, stat: &StatType) -> f32 {
-        let x = match self.increases().filter(|t| t == stat).is_some() {
-            true => 1.1,
-            false => 1.0,
-        };
-        let y = match self.decreases().filter(|t| t == stat).is_some() {
-            true => 0.9,
-            false => 1.0,
-        };
-        x * y
+        self.percent(stat) as f32 / 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::pokemon::stat::StatType;
+
+    use super::{Flavor, Nature};
+
+    #[test]
+    fn nature() {
+        for (i, nature) in Nature::ALL.into_iter().enumerate() {
+            assert_eq!(nature.index(), i);
+            assert_eq!(Nature::from_index(i), Some(nature));
+        }
+        assert_eq!(Nature::from_index(Nature::COUNT), None);
+
+        assert_eq!(Nature::Adamant.increases(), Some(StatType::Attack));
+        assert_eq!(Nature::Adamant.decreases(), Some(StatType::SpAttack));
+        assert_eq!(Nature::Adamant.likes(), Some(Flavor::Spicy));
+        assert_eq!(Nature::Adamant.dislikes(), Some(Flavor::Dry));
+        assert_eq!(Nature::Timid.decreases(), Some(StatType::Attack));
+        assert_eq!(Nature::Serious.likes(), None);
+        assert_eq!(Nature::ALL.iter().filter(|n| n.is_neutral()).count(), 5);
     }
 }
@@ -1,4 +1,7 @@
-use core::ops::RangeInclusive;
+use core::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    ops::RangeInclusive,
+};
 
 use alloc::{string::String, sync::Arc, vec::Vec};
 
@@ -13,10 +16,15 @@ use crate::{
         Move, MoveId, PP,
     },
     pokemon::{
-        stat::{BaseStat, StatType, Stats},
-        EvolutionType, Experience, Friendship, Gender, Health, Level, Nature, Pokemon, PokemonId,
+        personality::Personality,
+        stat::{
+            retro::{Dvs, StatExperience, StatSystem},
+            BaseStat, HyperTraining, Stat, StatSet, StatType, Stats,
+        },
+        Characteristic, EvolutionType, Experience, Friendship, Gender, Health, Level, Nature, Pokemon, PokemonId,
     },
-    Dex,
+    trainer::TrainerId,
+    Dex, DexSet, DexStorage,
 };
 
 // pub type HP = crate::MaximumNumber<Health>;
@@ -34,19 +42,43 @@ pub struct UserPokemonData {
     pub level: Level,
 
     /// The [Gender] of this pokemon.
-    pub gender: Gender,
+    /// Generated when initialized if not given.
+    #[serde(default)]
+    pub gender: Option<Gender>,
+
+    /// The [Nature] of this pokemon.
+    /// Generated when initialized if not given.
+    #[serde(default)]
+    pub nature: Option<Nature>,
+
+    /// A [Nature] that overrides which stats this pokemon's nature changes, such as from a mint.
+    #[serde(default)]
+    pub nature_override: Option<Nature>,
+
+    /// Which of this pokemon's two ability slots it uses.
+    /// Generated when initialized if not given.
+    #[serde(default)]
+    pub ability_slot: Option<u8>,
 
-    pub nature: Nature,
+    /// The personality value of this pokemon.
+    #[serde(default)]
+    pub personality: Option<Personality>,
 
     /// The [Health] of this pokemon.
     #[serde(default)]
     pub hp: Option<Health>,
 
-    #[serde(default = "Stats::default_iv")]
-    pub ivs: Stats,
+    /// The IVs of this pokemon.
+    /// Generated when initialized if not given.
+    #[serde(default, deserialize_with = "deserialize_ivs")]
+    pub ivs: Option<Stats>,
     #[serde(default)]
     pub evs: Stats,
 
+    /// Which of this pokemon's IVs have been hyper trained.
+    #[serde(default)]
+    pub hyper_training: HyperTraining,
+
     #[serde(default = "Pokemon::default_friendship")]
     pub friendship: Friendship,
 
@@ -67,63 +99,102 @@ pub struct UserPokemonData {
     pub experience: Experience,
 }
 
-#[derive(Debug, Clone, Copy)]
+fn deserialize_ivs<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Stats>, D::Error> {
+    #[derive(Deserialize)]
+    struct Ivs(#[serde(deserialize_with = "Stats::deserialize_ivs")] Stats);
+
+    Option::<Ivs>::deserialize(deserializer).map(|ivs| ivs.map(|Ivs(ivs)| ivs))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UserPokemonField {
-    Pokemon,
     Gender,
     Nature,
-    Health,
-    Moves,
-    Item,
+    AbilitySlot,
+    Ivs,
 }
 
-#[derive(Debug, Clone, Copy)]
-pub enum UserPokemonErrorType {
-    Missing,
-    CannotInitialize,
-}
-
-#[derive(Debug, Clone, Copy)]
-pub struct UserPokemonError {
-    pub error: UserPokemonErrorType,
-    pub reason: UserPokemonField,
+/// An error from initializing a [UserPokemonData].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserPokemonError {
+    /// The pokemon is not in the pokedex.
+    MissingPokemon(PokemonId),
+    /// A move is not in the movedex.
+    MissingMove {
+        /// The position of the move in the pokemon's moves.
+        index: usize,
+        id: MoveId,
+    },
+    /// The held item is not in the itemdex.
+    MissingItem(ItemId),
+    /// The field was not given and no random number generator was provided to generate it.
+    NoRandom(UserPokemonField),
+    /// The field was given, but is different from the one that comes from the given personality value.
+    Mismatch(UserPokemonField),
 }
 
-impl UserPokemonError {
-
-    pub fn missing(reason: UserPokemonField) -> Self {
-        Self {
-            error: UserPokemonErrorType::Missing,
-            reason,
+impl Display for UserPokemonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::MissingPokemon(id) => write!(f, "Could not find pokemon {}", id),
+            Self::MissingMove { index, id } => {
+                write!(f, "Could not find move \"{}\" at position {}", id.0, index)
+            }
+            Self::MissingItem(id) => write!(f, "Could not find held item {}", id),
+            Self::NoRandom(field) => write!(
+                f,
+                "Could not generate {:?} without a random number generator",
+                field
+            ),
+            Self::Mismatch(field) => write!(
+                f,
+                "{:?} does not match the one from the personality value",
+                field
+            ),
         }
     }
-
-    pub fn init(reason: UserPokemonField) -> Self {
-        Self { error: UserPokemonErrorType::CannotInitialize, reason }
-    }
-
 }
 
 /// The base struct for a pokemon owned by a player.
+///
+/// Fields that change this pokemon's stats are private,
+/// so its cached stats are recalculated whenever they are set.
 #[derive(Debug, Clone)]
 pub struct UserPokemon {
     /// Pokemon Identifier
-    pub pokemon: Arc<Pokemon>,
+    pokemon: Arc<Pokemon>,
 
     /// [Level] of the pokemon (1 - 100)
-    pub level: Level,
+    level: Level,
 
     /// The [Gender] of this pokemon.
     // #[serde(default)]
     pub gender: Gender,
 
-    pub nature: Nature,
+    nature: Nature,
+
+    /// A [Nature] that overrides which stats this pokemon's nature changes.
+    nature_override: Option<Nature>,
+
+    /// Which of this pokemon's two ability slots it uses.
+    pub ability_slot: u8,
+
+    /// The personality value of this pokemon.
+    pub personality: Option<Personality>,
 
     /// The [Health] of this pokemon.
     pub hp: Health,
 
-    pub ivs: Stats,
-    pub evs: Stats,
+    ivs: Stats,
+    evs: Stats,
+
+    /// Which of this pokemon's IVs have been hyper trained.
+    hyper_training: HyperTraining,
+
+    /// Cached stats, calculated from the fields above.
+    stats: StatSet<StatType, BaseStat>,
 
     pub friendship: Friendship,
 
@@ -140,6 +211,109 @@ pub struct UserPokemon {
 }
 
 impl UserPokemon {
+    /// Get the [Pokemon] this is.
+    pub fn pokemon(&self) -> &Arc<Pokemon> {
+        &self.pokemon
+    }
+
+    /// Change the [Pokemon] this is, such as when evolving.
+    pub fn set_pokemon(&mut self, pokemon: Arc<Pokemon>) {
+        self.pokemon = pokemon;
+        self.update_stats();
+    }
+
+    /// Get the [Level] of this pokemon.
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    /// Set the [Level] of this pokemon.
+    pub fn set_level(&mut self, level: Level) {
+        self.level = level;
+        self.update_stats();
+    }
+
+    /// Get the [Nature] of this pokemon.
+    pub fn nature(&self) -> Nature {
+        self.nature
+    }
+
+    /// Set the [Nature] of this pokemon.
+    pub fn set_nature(&mut self, nature: Nature) {
+        self.nature = nature;
+        self.update_stats();
+    }
+
+    /// Get the [Nature] that overrides which stats this pokemon's nature changes.
+    pub fn nature_override(&self) -> Option<Nature> {
+        self.nature_override
+    }
+
+    /// Set the [Nature] that overrides which stats this pokemon's nature changes, such as when using a mint.
+    pub fn set_nature_override(&mut self, nature: Option<Nature>) {
+        self.nature_override = nature;
+        self.update_stats();
+    }
+
+    /// Get the [Nature] used to calculate this pokemon's stats.
+    pub fn stat_nature(&self) -> Nature {
+        self.nature_override.unwrap_or(self.nature)
+    }
+
+    /// Get the IVs of this pokemon.
+    pub fn ivs(&self) -> &Stats {
+        &self.ivs
+    }
+
+    /// Set the IVs of this pokemon.
+    pub fn set_ivs(&mut self, ivs: Stats) {
+        self.ivs = ivs;
+        self.update_stats();
+    }
+
+    /// Get the EVs of this pokemon.
+    pub fn evs(&self) -> &Stats {
+        &self.evs
+    }
+
+    /// Set the EVs of this pokemon.
+    pub fn set_evs(&mut self, evs: Stats) {
+        self.evs = evs;
+        self.update_stats();
+    }
+
+    /// Increment one of this pokemon's EVs (see [Stats::increment_ev]).
+    pub fn increment_ev(&mut self, stat: StatType, by: Stat) {
+        self.evs.increment_ev(stat, by);
+        self.update_stats();
+    }
+
+    /// Get which of this pokemon's IVs have been hyper trained.
+    pub fn hyper_training(&self) -> &HyperTraining {
+        &self.hyper_training
+    }
+
+    /// Set which of this pokemon's IVs have been hyper trained.
+    pub fn set_hyper_training(&mut self, hyper_training: HyperTraining) {
+        self.hyper_training = hyper_training;
+        self.update_stats();
+    }
+
+    /// Get every [BaseStat] of this pokemon.
+    pub fn stats(&self) -> &StatSet<StatType, BaseStat> {
+        &self.stats
+    }
+
+    fn update_stats(&mut self) {
+        self.stats = self.pokemon.stats(
+            &self.ivs,
+            &self.hyper_training,
+            &self.evs,
+            self.level,
+            self.stat_nature(),
+        );
+    }
+
     /// Get the current HP of this pokemon.
     pub fn hp(&self) -> Health {
         self.hp
@@ -182,6 +356,69 @@ impl UserPokemon {
         self.pokemon.moves_at_level(self.level)
     }
 
+    /// Get the level up moves this pokemon could have learned by its current [Level] that it does not know.
+    pub fn relearnable_moves(&self) -> Vec<&MoveId> {
+        let mut moves = Vec::new();
+        for id in self.pokemon.moves_at(1..=self.level) {
+            if !moves.contains(&id) && !self.moves.iter().any(|m| m.id() == id) {
+                moves.push(id);
+            }
+        }
+        moves
+    }
+
+    /// Relearn a move from [UserPokemon::relearnable_moves].
+    /// If this pokemon already knows the maximum amount of moves, the move at `index` is replaced.
+    /// Returns false if the move cannot be relearned.
+    #[allow(deprecated)]
+    pub fn relearn(&mut self, id: &MoveId, index: Option<usize>, movedex: &Dex<Move, impl DexStorage<Move>>) -> bool {
+        if !self.relearnable_moves().contains(&id) {
+            return false;
+        }
+        let m = match movedex.try_get(id) {
+            Some(m) => UserMove::from(m.clone()),
+            None => return false,
+        };
+        match self.moves.len() < MOVE_MAX {
+            true => {
+                self.moves.push(m);
+                true
+            }
+            false => match index.and_then(|i| self.moves.get_mut(i)) {
+                Some(slot) => {
+                    *slot = m;
+                    true
+                }
+                None => false,
+            },
+        }
+    }
+
+    /// Check if this pokemon can be taught a move from a TM or HM.
+    pub fn can_learn_machine(&self, id: &MoveId) -> bool {
+        self.pokemon.learnset.machine(id)
+    }
+
+    /// Get the moves this pokemon knows that it could not have learned at its current [Level].
+    pub fn illegal_moves(&self) -> impl Iterator<Item = &UserMove> + '_ {
+        self.moves
+            .iter()
+            .filter(|m| !self.pokemon.can_learn_at(m.id(), self.level))
+    }
+
+    /// Get this pokemon's [Characteristic], which hints at its highest IV.
+    pub fn characteristic(&self) -> Characteristic {
+        Characteristic::new(&self.ivs, self.personality.map(|p| p.0).unwrap_or_default())
+    }
+
+    /// Check if this pokemon is shiny for its original trainer.
+    /// Pokemon without a personality value are never shiny.
+    pub fn is_shiny(&self, trainer: &TrainerId) -> bool {
+        self.personality
+            .map(|p| p.is_shiny(trainer))
+            .unwrap_or_default()
+    }
+
     /// Get the maximum [Health] of this pokemon.
     pub fn max_hp(&self) -> Health {
         self.stat(StatType::Health)
@@ -192,10 +429,28 @@ impl UserPokemon {
         self.hp() as f32 / self.max_hp() as f32
     }
 
+    /// Get the IVs used to calculate this pokemon's stats, including hyper training.
+    pub fn effective_ivs(&self) -> Stats {
+        self.ivs.hyper_trained(&self.hyper_training)
+    }
+
     /// Get a [BaseStat] for this pokemon.
     pub fn stat(&self, stat: StatType) -> BaseStat {
-        self.pokemon
-            .stat(&self.ivs, &self.evs, self.level, self.nature, stat)
+        self.stats[stat]
+    }
+
+    /// Get a [BaseStat] for this pokemon using the formulas of a [StatSystem].
+    /// Retro stats are calculated from DVs and stat experience converted from this pokemon's IVs and EVs.
+    pub fn system_stat(&self, system: StatSystem, stat: StatType) -> BaseStat {
+        match system {
+            StatSystem::Modern => self.stat(stat),
+            StatSystem::Retro => self.pokemon.retro_stat(
+                &Dvs::from_ivs(&self.ivs),
+                &StatExperience::from_evs(&self.evs),
+                self.level,
+                stat,
+            ),
+        }
     }
 
     /// Heal this pokemon with an optional amount of [Health].
@@ -230,14 +485,19 @@ impl UserPokemon {
             self.level += 1;
         }
 
+        if previous != self.level {
+            self.update_stats();
+        }
+
         previous..=self.level
     }
 
     /// Handle leveling up.
+    #[allow(deprecated)]
     pub fn fill_moves<'m>(
         &mut self,
         mut moves: impl DoubleEndedIterator<Item = &'m MoveId> + 'm,
-        movedex: &Dex<Move>,
+        movedex: &Dex<Move, impl DexStorage<Move>>,
     ) -> impl DoubleEndedIterator<Item = &'m MoveId> + 'm {
 
         // Add moves if the player's pokemon does not have a full set of moves.
@@ -261,11 +521,15 @@ impl UserPokemon {
             // data: OwnablePokemonData {
             pokemon: self.pokemon.id,
             level: self.level,
-            gender: self.gender.into(),
-            nature: self.nature.into(),
+            gender: Some(self.gender),
+            nature: Some(self.nature),
+            nature_override: self.nature_override,
+            ability_slot: Some(self.ability_slot),
+            personality: self.personality,
             hp: self.hp.into(),
-            ivs: self.ivs,
+            ivs: Some(self.ivs),
             evs: self.evs,
+            hyper_training: self.hyper_training,
             friendship: self.friendship,
             ailment: self.ailment,
             // },
@@ -280,42 +544,152 @@ impl UserPokemon {
 
 
 impl UserPokemonData {
-    /// Initialize a [SavedPokemon] that already has selfs given to its uninitialized fields
+    /// Initialize a [UserPokemon] from this data.
+    ///
+    /// Gender, nature, ability slot and IVs that are not given are generated with the random number generator.
+    /// Gender, nature and ability slot come from the pokemon's personality value,
+    /// which is also generated to match any given gender, nature and ability slot if it is not given
+    /// (see [Pokemon::generate_matching_personality]).
+    /// A given personality value must match any gender, nature and ability slot that are also given.
+    ///
+    /// Fails with every problem found, rather than just the first one.
+    #[allow(deprecated)]
     pub fn init<R: Rng>(
         &self,
-        pokedex: &Dex<Pokemon>,
-        movedex: &Dex<Move>,
-        itemdex: &Dex<Item>,
+        dexes: &DexSet<impl DexStorage<Pokemon>, impl DexStorage<Move>, impl DexStorage<Item>>,
         mut update: Option<&mut R>,
-    ) -> Result<UserPokemon, UserPokemonError> {
-
-        fn generate_or_error<R: Rng, T>(t: Option<T>, update: &mut Option<&mut R>, f: impl FnOnce(&mut R) -> T, field: UserPokemonField) -> Result<T, UserPokemonError> {
+    ) -> Result<UserPokemon, Vec<UserPokemonError>> {
+        fn generate_or_error<R: Rng, T>(
+            t: Option<T>,
+            update: &mut Option<&mut R>,
+            f: impl FnOnce(&mut R) -> T,
+            field: UserPokemonField,
+        ) -> Result<T, UserPokemonError> {
             match t {
                 Some(t) => Ok(t),
                 None => match update {
                     Some(r) => Ok((f)(r)),
-                    None => Err(UserPokemonError::init(field)),
+                    None => Err(UserPokemonError::NoRandom(field)),
                 }
             }
         }
 
-        let pokemon = pokedex.try_get(&self.pokemon).ok_or(UserPokemonError::missing(UserPokemonField::Pokemon))?;
-        // let gender = generate_or_error(self.gender, update, |random| pokemon.generate_gender(random), UserPokemonField::Gender)?;
-        // let nature = self.nature.ok_or_else(())?;
-        let hp = self.hp.unwrap_or_else(|| pokemon.stat(&self.ivs, &self.evs, self.level, self.nature, StatType::Health));
-        let mut moves = Vec::with_capacity(self.moves.len());
+        fn from_personality<T: PartialEq>(
+            t: Option<T>,
+            personality: Option<T>,
+            field: UserPokemonField,
+        ) -> Result<Option<T>, UserPokemonError> {
+            match (t, personality) {
+                (Some(t), Some(personality)) if t != personality => {
+                    Err(UserPokemonError::Mismatch(field))
+                }
+                (t, personality) => Ok(t.or(personality)),
+            }
+        }
+
+        let mut errors = Vec::new();
+
+        let pokemon = dexes.pokedex.try_get(&self.pokemon);
+        let generated = pokemon.map(|pokemon| {
+            let personality = match self.personality {
+                Some(personality) => Some(personality.attributes(pokemon, None)),
+                None => update.as_deref_mut().and_then(|random| {
+                    pokemon.generate_matching_personality(
+                        random,
+                        None,
+                        self.gender,
+                        self.nature,
+                        self.ability_slot,
+                    )
+                }),
+            };
+            let gender = from_personality(
+                self.gender,
+                personality.map(|p| p.gender),
+                UserPokemonField::Gender,
+            )
+            .and_then(|gender| {
+                generate_or_error(
+                    gender,
+                    &mut update,
+                    |random| pokemon.generate_gender(random),
+                    UserPokemonField::Gender,
+                )
+            });
+            let nature = from_personality(
+                self.nature,
+                personality.map(|p| p.nature),
+                UserPokemonField::Nature,
+            )
+            .and_then(|nature| {
+                generate_or_error(nature, &mut update, Pokemon::generate_nature, UserPokemonField::Nature)
+            });
+            let ability_slot = from_personality(
+                self.ability_slot,
+                personality.map(|p| p.ability_slot),
+                UserPokemonField::AbilitySlot,
+            )
+            .and_then(|ability_slot| {
+                generate_or_error(
+                    ability_slot,
+                    &mut update,
+                    |random| random.gen_range(0..2),
+                    UserPokemonField::AbilitySlot,
+                )
+            });
+            let ivs = generate_or_error(self.ivs, &mut update, Stats::random_iv, UserPokemonField::Ivs);
+            (personality.map(|p| p.personality), gender, nature, ability_slot, ivs)
+        });
 
-        for m in self.moves.iter() {
-            moves.push(m.init(movedex).ok_or(UserPokemonError::init(UserPokemonField::Moves))?);
+        let mut moves = Vec::with_capacity(self.moves.len());
+        for (index, m) in self.moves.iter().enumerate() {
+            match m.init(&dexes.movedex) {
+                Some(m) => moves.push(m),
+                None => errors.push(UserPokemonError::MissingMove { index, id: m.id }),
+            }
         }
 
-        let item = self.item.and_then(|ref id| itemdex.try_get(id));
+        let item = self.item.and_then(|id| {
+            let item = dexes.itemdex.try_get(&id);
+            if item.is_none() {
+                errors.push(UserPokemonError::MissingItem(id));
+            }
+            item
+        });
+
+        let (pokemon, personality, gender, nature, ability_slot, ivs) = match (pokemon, generated) {
+            (Some(pokemon), Some((personality, Ok(gender), Ok(nature), Ok(ability_slot), Ok(ivs))))
+                if errors.is_empty() =>
+            {
+                (pokemon, personality, gender, nature, ability_slot, ivs)
+            }
+            (.., generated) => {
+                let mut fields = match generated {
+                    Some((_, gender, nature, ability_slot, ivs)) => {
+                        let fields = [gender.err(), nature.err(), ability_slot.err(), ivs.err()];
+                        fields.into_iter().flatten().collect()
+                    }
+                    None => alloc::vec![UserPokemonError::MissingPokemon(self.pokemon)],
+                };
+                fields.append(&mut errors);
+                return Err(fields);
+            }
+        };
+
+        let stats = pokemon.stats(
+            &ivs,
+            &self.hyper_training,
+            &self.evs,
+            self.level,
+            self.nature_override.unwrap_or(nature),
+        );
+        let hp = self.hp.unwrap_or(stats[StatType::Health]);
 
         if update.is_some() && moves.is_empty() {
             let mut m = pokemon
                 .moves_at(1..=self.level)
                 .rev()
-                .flat_map(|id| movedex.try_get(id))
+                .flat_map(|id| dexes.movedex.try_get(id))
                 .collect::<alloc::vec::Vec<_>>();
             m.dedup_by(|a, b| a.id == b.id);
             m.truncate(MOVE_MAX);
@@ -328,11 +702,16 @@ impl UserPokemonData {
             // data: OwnablePokemonData {
             pokemon: pokemon.clone(),
             level: self.level,
-            gender: self.gender,
-            nature: self.nature,
+            gender,
+            nature,
+            nature_override: self.nature_override,
+            ability_slot,
+            personality,
             hp,
-            ivs: self.ivs,
+            ivs,
             evs: self.evs,
+            hyper_training: self.hyper_training,
+            stats,
             friendship: self.friendship,
             ailment: self.ailment,
             // },
@@ -343,4 +722,276 @@ impl UserPokemonData {
         })
     }
 
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+
+    use alloc::{vec, vec::Vec};
+
+    use crate::{
+        dex::tests::test_move,
+        item::{ItemId, ItemStack},
+        moves::{owned::UserMoveData, Move, MoveId},
+        pokemon::{
+            data::LearnableMove,
+            personality::Personality,
+            stat::{HyperTraining, StatType, Stats},
+            tests::{test_pokemon, test_user_pokemon},
+            Gender, Nature, Pokemon, PokemonId,
+        },
+        trainer::TrainerData,
+        Dex, DexSet,
+    };
+
+    use super::{UserPokemon, UserPokemonData, UserPokemonError, UserPokemonField};
+
+    #[test]
+    fn cached_stats() {
+        let mut pokedex = Dex::<Pokemon>::default();
+        pokedex.insert(test_pokemon(Vec::new()));
+        let dexes: DexSet = DexSet {
+            pokedex,
+            ..Default::default()
+        };
+
+        let mut pokemon = test_user_pokemon(10, Vec::new())
+            .init::<rand::rngs::mock::StepRng>(&dexes, None)
+            .unwrap();
+
+        let expected = |pokemon: &UserPokemon| {
+            pokemon.pokemon().stats(
+                pokemon.ivs(),
+                pokemon.hyper_training(),
+                pokemon.evs(),
+                pokemon.level(),
+                pokemon.stat_nature(),
+            )
+        };
+
+        assert_eq!(pokemon.stats(), &expected(&pokemon));
+        assert_eq!(pokemon.max_hp(), pokemon.hp());
+
+        pokemon.set_level(50);
+        pokemon.set_nature(Nature::Adamant);
+        pokemon.set_nature_override(Some(Nature::Modest));
+        assert_eq!(pokemon.stat_nature(), Nature::Modest);
+        pokemon.increment_ev(StatType::Attack, 100);
+        let mut training = HyperTraining::default();
+        training[StatType::Speed] = true;
+        pokemon.set_hyper_training(training);
+        assert_eq!(pokemon.stats(), &expected(&pokemon));
+
+        let level = pokemon.level();
+        pokemon.add_exp(100_000);
+        assert!(pokemon.level() > level);
+        assert_eq!(pokemon.stats(), &expected(&pokemon));
+    }
+
+    #[test]
+    fn generate() {
+        let mut pokedex = Dex::<Pokemon>::default();
+        let mut pokemon = test_pokemon(Vec::new());
+        pokemon.breeding.gender = Some(4);
+        pokedex.insert(pokemon);
+        let dexes: DexSet = DexSet {
+            pokedex,
+            ..Default::default()
+        };
+
+        let mut data = test_user_pokemon(5, Vec::new());
+        data.gender = None;
+        data.nature = None;
+        data.ability_slot = None;
+        data.ivs = None;
+
+        let error = data
+            .init::<rand::rngs::mock::StepRng>(&dexes, None)
+            .unwrap_err();
+        assert_eq!(
+            error,
+            [
+                UserPokemonError::NoRandom(UserPokemonField::Gender),
+                UserPokemonError::NoRandom(UserPokemonField::Nature),
+                UserPokemonError::NoRandom(UserPokemonField::AbilitySlot),
+                UserPokemonError::NoRandom(UserPokemonField::Ivs),
+            ]
+        );
+
+        let mut rng = rand::rngs::mock::StepRng::new(12, 24);
+        let pokemon = data
+            .init(&dexes, Some(&mut rng))
+            .unwrap();
+        assert_ne!(pokemon.gender, Gender::None);
+        assert!(pokemon.ivs().valid_ivs());
+
+        // gender and nature come from a generated personality value
+        let personality = pokemon.personality.unwrap();
+        assert_eq!(pokemon.nature(), personality.nature());
+        assert_eq!(pokemon.gender, personality.gender(pokemon.pokemon()));
+        assert_eq!(pokemon.ability_slot, personality.ability_slot());
+
+        data.personality = Some(Personality(14));
+        let pokemon = data
+            .init(&dexes, Some(&mut rng))
+            .unwrap();
+        assert_eq!(pokemon.nature(), Nature::Naive);
+        assert_eq!(pokemon.gender, Gender::Female);
+
+        // given fields must match a given personality value
+        data.gender = Some(Gender::Female);
+        data.nature = Some(Nature::Naive);
+        assert!(data.init(&dexes, Some(&mut rng)).is_ok());
+        data.gender = Some(Gender::Male);
+        data.nature = Some(Nature::Adamant);
+        data.ability_slot = Some(1);
+        let error = data.init(&dexes, Some(&mut rng)).unwrap_err();
+        assert_eq!(
+            error,
+            [
+                UserPokemonError::Mismatch(UserPokemonField::Gender),
+                UserPokemonError::Mismatch(UserPokemonField::Nature),
+                UserPokemonError::Mismatch(UserPokemonField::AbilitySlot),
+            ]
+        );
+        assert_eq!(
+            error[1].to_string(),
+            "Nature does not match the one from the personality value"
+        );
+
+        // a generated personality value matches a given gender, nature and ability slot
+        data.personality = None;
+        data.gender = Some(Gender::Male);
+        data.nature = Some(Nature::Adamant);
+        data.ability_slot = Some(1);
+        let mut odd = rand::rngs::mock::StepRng::new(13, 7);
+        let pokemon = data
+            .init(&dexes, Some(&mut odd))
+            .unwrap();
+        let personality = pokemon.personality.unwrap();
+        assert_eq!(pokemon.nature(), Nature::Adamant);
+        assert_eq!(personality.nature(), pokemon.nature());
+        assert_eq!(personality.gender(pokemon.pokemon()), Gender::Male);
+        assert_eq!(personality.ability_slot(), 1);
+
+        // no personality value can match a gender this pokemon cannot have
+        data.gender = Some(Gender::None);
+        let pokemon = data
+            .init(&dexes, Some(&mut rng))
+            .unwrap();
+        assert_eq!(pokemon.gender, Gender::None);
+        assert!(pokemon.personality.is_none());
+    }
+
+    #[test]
+    fn ivs_round_trip() {
+        let mut data = test_user_pokemon(5, Vec::new());
+        data.ivs = None;
+        let json = serde_json::to_string(&data).unwrap();
+        assert!(serde_json::from_str::<UserPokemonData>(&json).unwrap().ivs.is_none());
+
+        data.ivs = Some(Stats::uniform(Stats::MAX_IV));
+        let json = serde_json::to_string(&data).unwrap();
+        assert_eq!(serde_json::from_str::<UserPokemonData>(&json).unwrap().ivs, data.ivs);
+
+        data.ivs = Some(Stats::uniform(Stats::MAX_IV + 1));
+        let json = serde_json::to_string(&data).unwrap();
+        assert!(serde_json::from_str::<UserPokemonData>(&json).is_err());
+    }
+
+    #[test]
+    fn init_errors() {
+        let mut pokedex = Dex::<Pokemon>::default();
+        pokedex.insert(test_pokemon(Vec::new()));
+
+        let missing: MoveId = "missing".parse().unwrap();
+        let item: ItemId = "item".parse().unwrap();
+
+        let mut rng = rand::rngs::mock::StepRng::new(12, 24);
+        let dexes: DexSet = DexSet {
+            pokedex,
+            ..Default::default()
+        };
+
+        let mut data = test_user_pokemon(5, vec![UserMoveData::from(missing); 2]);
+        data.item = Some(item);
+        let errors = data.init(&dexes, Some(&mut rng)).unwrap_err();
+        assert_eq!(
+            errors,
+            [
+                UserPokemonError::MissingMove { index: 0, id: missing },
+                UserPokemonError::MissingMove { index: 1, id: missing },
+                UserPokemonError::MissingItem(item),
+            ]
+        );
+        assert_eq!(errors[0].to_string(), "Could not find move \"missing\" at position 0");
+
+        data.moves.clear();
+        let valid = test_user_pokemon(5, Vec::new());
+        let mut unknown = test_user_pokemon(5, Vec::new());
+        unknown.pokemon = PokemonId(1);
+
+        let trainer = TrainerData {
+            party: vec![valid, data, unknown],
+            bag: ["zebra", "item", "apple", "mango"]
+                .into_iter()
+                .map(|id| ItemStack { item: id.parse().unwrap(), count: 1 })
+                .collect::<Vec<_>>()
+                .into(),
+            ..Default::default()
+        };
+        let error = trainer.init(&mut rng, &dexes).unwrap_err();
+        assert_eq!(
+            error.party,
+            [
+                (1, vec![UserPokemonError::MissingItem(item)]),
+                (2, vec![UserPokemonError::MissingPokemon(PokemonId(1))])
+            ]
+        );
+        let mut missing: Vec<ItemId> = ["apple", "item", "mango", "zebra"]
+            .into_iter()
+            .map(|id| id.parse().unwrap())
+            .collect();
+        missing.sort();
+        assert_eq!(error.bag, missing);
+    }
+
+    #[test]
+    fn relearn() {
+        let ids: Vec<MoveId> = ["a", "b", "c", "d", "e", "f"]
+            .into_iter()
+            .map(|id| id.parse().unwrap())
+            .collect();
+
+        let mut pokedex = Dex::<Pokemon>::default();
+        pokedex.insert(test_pokemon(
+            ids.iter()
+                .enumerate()
+                .map(|(level, id)| LearnableMove(level as u8 * 5 + 1, *id))
+                .collect(),
+        ));
+
+        let mut movedex = Dex::<Move>::default();
+        for id in ids.iter() {
+            movedex.insert(test_move(id.0.as_str(), id.0.as_str()));
+        }
+        let dexes: DexSet = DexSet {
+            pokedex,
+            movedex,
+            ..Default::default()
+        };
+
+        let mut pokemon = test_user_pokemon(22, vec![UserMoveData::from(ids[1])])
+        .init::<rand::rngs::mock::StepRng>(&dexes, None)
+        .unwrap();
+
+        assert_eq!(pokemon.relearnable_moves(), [&ids[0], &ids[2], &ids[3], &ids[4]]);
+        assert!(!pokemon.relearn(&ids[5], None, &dexes.movedex));
+        assert!(pokemon.relearn(&ids[0], None, &dexes.movedex));
+        assert!(pokemon.relearn(&ids[2], None, &dexes.movedex));
+        assert!(pokemon.relearn(&ids[3], None, &dexes.movedex));
+        assert!(!pokemon.relearn(&ids[4], None, &dexes.movedex));
+        assert!(pokemon.relearn(&ids[4], Some(0), &dexes.movedex));
+        assert_eq!(pokemon.relearnable_moves(), [&ids[1]]);
+    }
+}
@@ -0,0 +1,136 @@
+//! Personality values, which decide many attributes of a pokemon at once.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::trainer::TrainerId;
+
+use super::{Gender, Nature, Pokemon};
+
+/// A random 32 bit number given to a pokemon when it is created.
+/// Its gender, nature, ability slot and shininess all come from it,
+/// so they stay consistent with each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Personality(pub u32);
+
+/// The attributes of a pokemon that come from its [Personality].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PersonalityAttributes {
+    pub personality: Personality,
+    pub gender: Gender,
+    pub nature: Nature,
+    pub ability_slot: u8,
+    /// Only known if the pokemon's original trainer is.
+    pub shiny: bool,
+}
+
+impl Personality {
+    /// Pokemon are shiny when their shiny value is below this.
+    pub const SHINY_THRESHOLD: u16 = 8;
+
+    /// Generate a random personality value.
+    pub fn random(random: &mut impl Rng) -> Self {
+        Self(random.gen())
+    }
+
+    /// Generate a personality value that is shiny for a trainer.
+    pub fn random_shiny(random: &mut impl Rng, trainer: &TrainerId) -> Self {
+        let low: u16 = random.gen();
+        let shiny: u16 = random.gen_range(0..Self::SHINY_THRESHOLD);
+        let high = trainer.id ^ trainer.secret ^ low ^ shiny;
+        Self((high as u32) << 16 | low as u32)
+    }
+
+    /// Get every attribute that comes from this personality value.
+    pub fn attributes(&self, pokemon: &Pokemon, trainer: Option<&TrainerId>) -> PersonalityAttributes {
+        PersonalityAttributes {
+            personality: *self,
+            gender: self.gender(pokemon),
+            nature: self.nature(),
+            ability_slot: self.ability_slot(),
+            shiny: trainer.map(|t| self.is_shiny(t)).unwrap_or_default(),
+        }
+    }
+
+    /// The [Nature] of this personality value.
+    pub const fn nature(&self) -> Nature {
+        Nature::ALL[(self.0 % Nature::COUNT as u32) as usize]
+    }
+
+    /// The [Gender] of a pokemon with this personality value.
+    /// This uses the same gender chances as [Pokemon::generate_gender].
+    pub fn gender(&self, pokemon: &Pokemon) -> Gender {
+        let value = (self.0 & 0xFF) as u8 >> 5;
+        pokemon
+            .breeding
+            .gender
+            .map(|percentage| match value > percentage {
+                true => Gender::Male,
+                false => Gender::Female,
+            })
+            .unwrap_or(Gender::None)
+    }
+
+    /// Which of a pokemon's two ability slots this personality value uses.
+    pub const fn ability_slot(&self) -> u8 {
+        (self.0 & 1) as u8
+    }
+
+    /// The shiny value of this personality value for a trainer.
+    pub const fn shiny_value(&self, trainer: &TrainerId) -> u16 {
+        trainer.id ^ trainer.secret ^ (self.0 >> 16) as u16 ^ self.0 as u16
+    }
+
+    /// Check if a pokemon with this personality value is shiny for its original trainer.
+    pub const fn is_shiny(&self, trainer: &TrainerId) -> bool {
+        self.shiny_value(trainer) < Self::SHINY_THRESHOLD
+    }
+}
+
+impl From<u32> for Personality {
+    fn from(value: u32) -> Self {
+        Self(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use alloc::vec::Vec;
+
+    use crate::{
+        pokemon::{tests::test_pokemon, Gender, Nature},
+        trainer::TrainerId,
+    };
+
+    use super::Personality;
+
+    #[test]
+    fn personality() {
+        let trainer = TrainerId {
+            id: 24294,
+            secret: 38834,
+        };
+        let personality = Personality(2047040214);
+        assert_eq!(personality.nature(), Nature::Naive);
+        assert_eq!(personality.ability_slot(), 0);
+        assert!(!personality.is_shiny(&trainer));
+
+        let mut pokemon = test_pokemon(Vec::new());
+        pokemon.breeding.gender = Some(3);
+        assert_eq!(personality.gender(&pokemon), Gender::Male);
+        pokemon.breeding.gender = Some(7);
+        assert_eq!(personality.gender(&pokemon), Gender::Female);
+
+        let mut rng = rand::rngs::mock::StepRng::new(12, 24);
+        for _ in 0..16 {
+            let shiny = Personality::random_shiny(&mut rng, &trainer);
+            assert!(shiny.attributes(&pokemon, Some(&trainer)).shiny);
+        }
+
+        let attributes = pokemon.generate_personality(&mut rng, None);
+        assert_eq!(attributes.nature, attributes.personality.nature());
+        assert!(!attributes.shiny);
+    }
+}
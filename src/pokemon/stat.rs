@@ -1,6 +1,7 @@
 use core::{
     iter::Sum,
-    ops::{Index, IndexMut, Range},
+    num::TryFromIntError,
+    ops::{Add, Index, IndexMut, Mul, RangeInclusive, Sub},
 };
 
 use enum_map::{EnumArray, EnumMap};
@@ -10,6 +11,11 @@ use rand::{
 };
 use serde::{Deserialize, Serialize};
 
+pub mod calculator;
+pub mod hidden_power;
+pub mod retro;
+pub mod stage;
+
 #[macro_export]
 macro_rules! stat_set {
     {$($t:tt)*} => {
@@ -28,6 +34,10 @@ pub type BaseStat = u16;
 /// A [StatSet] of [Stat]s.
 pub type Stats = StatSet<StatType, Stat>;
 
+/// Which IVs have been hyper trained.
+/// A hyper trained stat is calculated as if its IV was [Stats::MAX_IV].
+pub type HyperTraining = StatSet<StatType, bool>;
+
 /// The type of [Stat].
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Enum, Serialize, Deserialize,
@@ -62,6 +72,11 @@ impl<K: EnumArray<S>, S> StatSet<K, S> {
         Self(map)
     }
 
+    /// Create a [StatSet] from a function that gives the stat of each [StatType].
+    pub fn from_fn(f: impl FnMut(K) -> S) -> Self {
+        Self(EnumMap::from_fn(f))
+    }
+
     /// Get a random [StatSet]
     pub fn random<R: SampleRange<S> + Clone>(random: &mut impl Rng, range: R) -> Self
     where
@@ -81,35 +96,181 @@ impl<K: EnumArray<S>, S> StatSet<K, S> {
     {
         self.0.values().cloned().sum()
     }
+
+    /// Iterate over every [StatType] and its stat.
+    pub fn iter(&self) -> enum_map::Iter<'_, K, S> {
+        self.0.iter()
+    }
+
+    /// Iterate mutably over every [StatType] and its stat.
+    pub fn iter_mut(&mut self) -> enum_map::IterMut<'_, K, S> {
+        self.0.iter_mut()
+    }
+
+    /// Create a new [StatSet] by applying a function to each stat.
+    pub fn map<T>(self, f: impl FnMut(K, S) -> T) -> StatSet<K, T>
+    where
+        K: EnumArray<T>,
+    {
+        StatSet(self.0.map(f))
+    }
+
+    /// Create a new [StatSet] by combining the stats of two [StatSet]s.
+    pub fn zip_with<T, U>(
+        self,
+        other: StatSet<K, T>,
+        mut f: impl FnMut(K, S, T) -> U,
+    ) -> StatSet<K, U>
+    where
+        K: EnumArray<T> + EnumArray<U>,
+    {
+        let mut other = other.0.into_values();
+        self.map(|k, s| {
+            // both sets have a value for every key
+            f(k, s, other.next().unwrap())
+        })
+    }
+}
+
+impl<K: EnumArray<S>, S: Add<Output = S>> Add for StatSet<K, S> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |_, a, b| a + b)
+    }
+}
+
+impl<K: EnumArray<S>, S: Sub<Output = S>> Sub for StatSet<K, S> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |_, a, b| a - b)
+    }
+}
+
+impl<K: EnumArray<S>, S: Mul<Output = S>> Mul for StatSet<K, S> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |_, a, b| a * b)
+    }
+}
+
+macro_rules! saturating {
+    ($($s:ty),*) => {
+        $(
+            impl<K: EnumArray<$s>> StatSet<K, $s> {
+                /// Add each stat, stopping at the maximum value instead of overflowing.
+                pub fn saturating_add(self, rhs: Self) -> Self {
+                    self.zip_with(rhs, |_, a, b| a.saturating_add(b))
+                }
+
+                /// Subtract each stat, stopping at zero instead of overflowing.
+                pub fn saturating_sub(self, rhs: Self) -> Self {
+                    self.zip_with(rhs, |_, a, b| a.saturating_sub(b))
+                }
+
+                /// Multiply each stat, stopping at the maximum value instead of overflowing.
+                pub fn saturating_mul(self, rhs: Self) -> Self {
+                    self.zip_with(rhs, |_, a, b| a.saturating_mul(b))
+                }
+            }
+        )*
+    };
+}
+
+saturating!(Stat, BaseStat);
+
+impl<K: EnumArray<Stat> + EnumArray<BaseStat>> From<StatSet<K, Stat>> for StatSet<K, BaseStat> {
+    fn from(stats: StatSet<K, Stat>) -> Self {
+        stats.map(|_, s| s.into())
+    }
+}
+
+impl<K: EnumArray<Stat> + EnumArray<BaseStat>> TryFrom<StatSet<K, BaseStat>> for StatSet<K, Stat>
+{
+    type Error = TryFromIntError;
+
+    fn try_from(stats: StatSet<K, BaseStat>) -> Result<Self, Self::Error> {
+        let mut error = None;
+        let stats = stats.map(|_, s| {
+            Stat::try_from(s).unwrap_or_else(|e| {
+                error = Some(e);
+                Default::default()
+            })
+        });
+        match error {
+            Some(e) => Err(e),
+            None => Ok(stats),
+        }
+    }
 }
 
 impl Stats {
     /// The maximum value of an IV stat.
-    pub const MAX_IV: Stat = 32;
+    pub const MAX_IV: Stat = 31;
     /// The maximum value of an EV stat.
     pub const MAX_EV: Stat = 252;
 
     /// The maximum amount of all EVs added up in a [StatSet].
-    pub const MAX_EVS_TOTAL: u16 = 512;
+    pub const MAX_EVS_TOTAL: u16 = 510;
 
     /// The numerical range of an IV stat.
-    pub const IV_RANGE: Range<Stat> = 0..Self::MAX_IV;
+    pub const IV_RANGE: RangeInclusive<Stat> = 0..=Self::MAX_IV;
     /// The numerical range of an EV stat.
-    pub const EV_RANGE: Range<Stat> = 0..Self::MAX_EV;
+    pub const EV_RANGE: RangeInclusive<Stat> = 0..=Self::MAX_EV;
 
     /// Generate a random [Stat] in the IV_RANGE
     pub fn random_iv(random: &mut impl Rng) -> Self {
         Self::random(random, Self::IV_RANGE)
     }
 
+    /// Check if every stat is a valid IV.
+    pub fn valid_ivs(&self) -> bool {
+        self.0.values().all(|iv| Self::IV_RANGE.contains(iv))
+    }
+
+    /// Deserialize IVs, failing if any IV is above [Stats::MAX_IV].
+    pub fn deserialize_ivs<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let ivs = Self::deserialize(deserializer)?;
+        match ivs.valid_ivs() {
+            true => Ok(ivs),
+            false => Err(serde::de::Error::custom(format_args!(
+                "IVs cannot be above {}",
+                Self::MAX_IV
+            ))),
+        }
+    }
+
+    /// Get the IVs used to calculate stats, with hyper trained stats set to [Stats::MAX_IV].
+    pub fn hyper_trained(&self, training: &HyperTraining) -> Self {
+        self.zip_with(*training, |_, iv, trained| match trained {
+            true => Self::MAX_IV,
+            false => iv,
+        })
+    }
+
+    /// Get the total of a pokemon's EV stats.
+    /// Unlike [StatSet::total], this cannot overflow.
+    pub fn ev_total(&self) -> u16 {
+        self.0.values().map(|ev| *ev as u16).sum()
+    }
+
+    /// Get how many more EVs a pokemon can gain before reaching [Stats::MAX_EVS_TOTAL].
+    pub fn evs_left(&self) -> u16 {
+        Self::MAX_EVS_TOTAL.saturating_sub(self.ev_total())
+    }
+
     /// Increment a [StatType] by an amount.
     /// This function is for a pokemon's EV stats.
+    /// The stat is capped at [Stats::MAX_EV], and all stats together at [Stats::MAX_EVS_TOTAL].
     pub fn increment_ev(&mut self, stat: StatType, by: Stat) {
-        if self.total() + by < Self::MAX_EV {
-            let stat = &mut self.0[stat];
-
-            *stat = stat.saturating_add(by);
-        }
+        let left = self.evs_left();
+        let stat = &mut self.0[stat];
+        let by = by.min(Self::MAX_EV.saturating_sub(*stat)) as u16;
+        *stat += by.min(left) as Stat;
     }
 
     /// Get the default IV [StatSet].
@@ -162,3 +323,69 @@ impl<'de, K: EnumArray<S> + EnumArray<Option<S>> + Deserialize<'de>, S: Deserial
         EnumMap::deserialize(deserializer).map(Self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::{BaseStat, HyperTraining, Stat, StatSet, StatType, Stats};
+
+    #[test]
+    fn increment_ev() {
+        let mut evs = Stats::default();
+        evs[StatType::Attack] = 200;
+        evs[StatType::Speed] = 100;
+        evs.increment_ev(StatType::Health, 4);
+        assert_eq!(evs[StatType::Health], 4);
+
+        evs.increment_ev(StatType::Attack, 100);
+        assert_eq!(evs[StatType::Attack], Stats::MAX_EV);
+        assert!(Stats::EV_RANGE.contains(&evs[StatType::Attack]));
+
+        // 506 of 510 used
+        evs[StatType::Defense] = 150;
+        evs.increment_ev(StatType::Health, 10);
+        assert_eq!(evs[StatType::Health], 8);
+        assert_eq!(evs.ev_total(), Stats::MAX_EVS_TOTAL);
+        assert_eq!(evs.evs_left(), 0);
+        evs.increment_ev(StatType::SpAttack, 10);
+        assert_eq!(evs[StatType::SpAttack], 0);
+    }
+
+    #[test]
+    fn stat_set() {
+        let a = Stats::uniform(200);
+        let mut b = Stats::uniform(100);
+        b[StatType::Speed] = 10;
+
+        assert_eq!((a - b)[StatType::Health], 100);
+        assert_eq!((a - b)[StatType::Speed], 190);
+        assert_eq!(a.saturating_add(b)[StatType::Health], Stat::MAX);
+        assert_eq!(b.saturating_sub(a), Stats::uniform(0));
+        assert_eq!(b.map(|_, s| s / 10) * Stats::uniform(2), {
+            let mut c = Stats::uniform(20);
+            c[StatType::Speed] = 2;
+            c
+        });
+
+        let wide = StatSet::<StatType, BaseStat>::from(a) + StatSet::from(a);
+        assert_eq!(wide, StatSet::uniform(400));
+        assert!(Stats::try_from(wide).is_err());
+        assert_eq!(Stats::try_from(StatSet::<StatType, BaseStat>::from(b)), Ok(b));
+
+        assert_eq!(b.iter().filter(|(.., s)| **s == 100).count(), 5);
+    }
+
+    #[test]
+    fn hyper_training() {
+        let mut ivs = Stats::uniform(Stats::MAX_IV);
+        assert!(ivs.valid_ivs());
+        ivs[StatType::Speed] = Stats::MAX_IV + 1;
+        assert!(!ivs.valid_ivs());
+
+        ivs[StatType::Speed] = 0;
+        let mut training = HyperTraining::default();
+        training[StatType::Speed] = true;
+        assert_eq!(ivs.hyper_trained(&training), Stats::uniform(Stats::MAX_IV));
+        assert_eq!(ivs[StatType::Speed], 0);
+    }
+}
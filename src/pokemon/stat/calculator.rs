@@ -0,0 +1,171 @@
+//! Work out the possible IVs of a pokemon from its stats.
+
+use core::ops::RangeInclusive;
+
+use crate::pokemon::{Level, Nature, Pokemon};
+
+use super::{BaseStat, HyperTraining, Stat, StatSet, StatType, Stats};
+
+/// A possible range of IVs for one [StatType].
+/// [None] means no IV can give the observed stats.
+pub type IvRange = Option<RangeInclusive<Stat>>;
+
+/// Narrows down the IVs a pokemon can have from the stats it has at different levels.
+///
+/// Hyper trained stats do not depend on their IV, so observing them only checks that the stat matches.
+#[derive(Debug, Clone)]
+pub struct IvCalculator<'p> {
+    pub pokemon: &'p Pokemon,
+    pub nature: Nature,
+    pub training: HyperTraining,
+    ranges: StatSet<StatType, IvRange>,
+}
+
+impl<'p> IvCalculator<'p> {
+    /// Create a calculator where every IV is possible.
+    pub fn new(pokemon: &'p Pokemon, nature: Nature) -> Self {
+        Self {
+            pokemon,
+            nature,
+            training: Default::default(),
+            ranges: StatSet::uniform(Some(Stats::IV_RANGE)),
+        }
+    }
+
+    /// Narrow down the IVs using every stat of a pokemon at a [Level].
+    pub fn observe(
+        &mut self,
+        level: Level,
+        evs: &Stats,
+        stats: &StatSet<StatType, BaseStat>,
+    ) -> &mut Self {
+        for (stat, value) in stats.iter() {
+            self.observe_stat(level, evs, stat, *value);
+        }
+        self
+    }
+
+    /// Narrow down the IVs of one [StatType] using its value at a [Level].
+    pub fn observe_stat(
+        &mut self,
+        level: Level,
+        evs: &Stats,
+        stat: StatType,
+        value: BaseStat,
+    ) -> &mut Self {
+        let observed = match self.training[stat] {
+            true => {
+                let trained = self.pokemon.stat(
+                    &Stats::default(),
+                    &self.training,
+                    evs,
+                    level,
+                    self.nature,
+                    stat,
+                );
+                (trained == value).then_some(Stats::IV_RANGE)
+            }
+            false => self.pokemon.iv_range(evs, level, self.nature, stat, value),
+        };
+        let range = &mut self.ranges[stat];
+        *range = match (range.take(), observed) {
+            (Some(range), Some(observed)) => {
+                let start = *range.start().max(observed.start());
+                let end = *range.end().min(observed.end());
+                (start <= end).then_some(start..=end)
+            }
+            _ => None,
+        };
+        self
+    }
+
+    /// Get the possible IVs of a [StatType].
+    pub fn range(&self, stat: StatType) -> &IvRange {
+        &self.ranges[stat]
+    }
+
+    /// Get the possible IVs of every [StatType].
+    pub fn ranges(&self) -> &StatSet<StatType, IvRange> {
+        &self.ranges
+    }
+
+    /// Get the IVs if every one of them is known exactly.
+    pub fn exact(&self) -> Option<Stats> {
+        let mut ivs = Stats::default();
+        for (stat, iv) in ivs.iter_mut() {
+            match self.ranges[stat].as_ref() {
+                Some(range) if range.start() == range.end() => *iv = *range.start(),
+                _ => return None,
+            }
+        }
+        Some(ivs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use alloc::vec::Vec;
+
+    use crate::{
+        pokemon::{
+            stat::{HyperTraining, StatSet, StatType, Stats},
+            tests::test_pokemon,
+            Nature,
+        },
+        stat_set,
+    };
+
+    use super::IvCalculator;
+
+    #[test]
+    fn iv_calculator() {
+        let mut pokemon = test_pokemon(Vec::new());
+        pokemon.base = stat_set! {
+            StatType::Health => 108,
+            StatType::Attack => 130,
+            StatType::Defense => 95,
+            StatType::SpAttack => 80,
+            StatType::SpDefense => 85,
+            StatType::Speed => 102,
+        };
+        let ivs = stat_set! {
+            StatType::Health => 24,
+            StatType::Attack => 12,
+            StatType::Defense => 30,
+            StatType::SpAttack => 16,
+            StatType::SpDefense => 23,
+            StatType::Speed => 5,
+        };
+        let evs = Stats::uniform(84);
+        let nature = Nature::Adamant;
+
+        let stats = |level| pokemon.stats(&ivs, &HyperTraining::default(), &evs, level, nature);
+
+        let mut calculator = IvCalculator::new(&pokemon, nature);
+        calculator.observe(20, &evs, &stats(20));
+        assert!(calculator.exact().is_none());
+        calculator.observe(78, &evs, &stats(78)).observe(100, &evs, &stats(100));
+        assert_eq!(calculator.exact(), Some(ivs));
+
+        calculator.observe_stat(100, &evs, StatType::Speed, 1);
+        assert_eq!(calculator.range(StatType::Speed), &None);
+
+        let mut training = HyperTraining::default();
+        training[StatType::Attack] = true;
+        let stats = |level| pokemon.stats(&ivs, &training, &evs, level, nature);
+
+        let mut calculator = IvCalculator::new(&pokemon, nature);
+        calculator.training = training;
+        calculator.observe(78, &evs, &stats(78)).observe(100, &evs, &stats(100));
+        assert_eq!(calculator.range(StatType::Attack), &Some(Stats::IV_RANGE));
+        assert_eq!(calculator.range(StatType::Defense), &Some(30..=30));
+        assert_eq!(
+            stats(100)[StatType::Attack],
+            pokemon.stat(&Stats::uniform(Stats::MAX_IV), &HyperTraining::default(), &evs, 100, nature, StatType::Attack)
+        );
+
+        calculator.observe_stat(100, &evs, StatType::Attack, 1);
+        assert_eq!(calculator.range(StatType::Attack), &None);
+    }
+}
@@ -0,0 +1,116 @@
+//! Hidden Power's type and power, which come from a pokemon's IVs.
+
+use crate::{moves::Power, types::PokemonType};
+
+use super::{StatType, Stats};
+
+/// The types Hidden Power can be, in the order of the formula.
+pub const HIDDEN_POWER_TYPES: [PokemonType; 16] = [
+    PokemonType::Fighting,
+    PokemonType::Flying,
+    PokemonType::Poison,
+    PokemonType::Ground,
+    PokemonType::Rock,
+    PokemonType::Bug,
+    PokemonType::Ghost,
+    PokemonType::Steel,
+    PokemonType::Fire,
+    PokemonType::Water,
+    PokemonType::Grass,
+    PokemonType::Electric,
+    PokemonType::Psychic,
+    PokemonType::Ice,
+    PokemonType::Dragon,
+    PokemonType::Dark,
+];
+
+/// The order stats are read in by the Hidden Power formula.
+const ORDER: [StatType; 6] = [
+    StatType::Health,
+    StatType::Attack,
+    StatType::Defense,
+    StatType::Speed,
+    StatType::SpAttack,
+    StatType::SpDefense,
+];
+
+impl Stats {
+    /// Get the type of Hidden Power from these IVs.
+    pub fn hidden_power_type(&self) -> PokemonType {
+        HIDDEN_POWER_TYPES[self.hidden_power_bits(0) as usize * 15 / 63]
+    }
+
+    /// Get the base power of Hidden Power from these IVs (30 - 70).
+    /// Hidden Power's power is always 60 from the sixth generation games onwards.
+    pub fn hidden_power_power(&self) -> Power {
+        (self.hidden_power_bits(1) as u16 * 40 / 63 + 30) as _
+    }
+
+    /// Get IVs that give Hidden Power a type, changing as few of these IVs as possible.
+    /// Only the lowest bit of each IV is changed.
+    /// Returns [None] if Hidden Power cannot be the type.
+    pub fn with_hidden_power(&self, pokemon_type: PokemonType) -> Option<Self> {
+        Self::hidden_power_spreads(self, pokemon_type)
+            .min_by_key(|ivs| ORDER.iter().filter(|s| ivs[**s] != self[**s]).count())
+    }
+
+    /// Get every spread of IVs that gives Hidden Power a type,
+    /// made by changing the lowest bit of each IV in a base spread.
+    pub fn hidden_power_spreads(
+        base: &Self,
+        pokemon_type: PokemonType,
+    ) -> impl Iterator<Item = Self> + '_ {
+        (0..64u8)
+            .map(|bits| {
+                let mut ivs = *base;
+                for (i, stat) in ORDER.iter().enumerate() {
+                    ivs[*stat] = (ivs[*stat] & !1) | (bits >> i & 1);
+                }
+                ivs
+            })
+            .filter(move |ivs| ivs.hidden_power_type() == pokemon_type)
+    }
+
+    fn hidden_power_bits(&self, bit: u8) -> u8 {
+        ORDER
+            .iter()
+            .enumerate()
+            .map(|(i, stat)| (self[*stat] >> bit & 1) << i)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{
+        pokemon::stat::{StatSet, StatType, Stats},
+        stat_set,
+        types::PokemonType,
+    };
+
+    #[test]
+    fn hidden_power() {
+        let ivs = Stats::uniform(Stats::MAX_IV);
+        assert_eq!(ivs.hidden_power_type(), PokemonType::Dark);
+        assert_eq!(ivs.hidden_power_power(), 70);
+
+        let ivs = stat_set! {
+            StatType::Health => 31,
+            StatType::Attack => 30,
+            StatType::Defense => 31,
+            StatType::SpAttack => 30,
+            StatType::SpDefense => 31,
+            StatType::Speed => 30,
+        };
+        assert_eq!(ivs.hidden_power_type(), PokemonType::Fire);
+
+        let max = Stats::uniform(Stats::MAX_IV);
+        let fire = max.with_hidden_power(PokemonType::Fire).unwrap();
+        assert_eq!(fire.hidden_power_type(), PokemonType::Fire);
+        assert_eq!(max.total() - fire.total(), 3);
+        assert_eq!(Stats::hidden_power_spreads(&max, PokemonType::Ice).count(), 4);
+
+        assert!(ivs.with_hidden_power(PokemonType::Normal).is_none());
+    }
+}
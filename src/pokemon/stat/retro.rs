@@ -0,0 +1,212 @@
+//! Stats from the first and second generation games.
+//!
+//! These use DVs and stat experience instead of IVs and EVs, and have one Special stat.
+
+use enum_map::Enum;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{Stat, StatSet, StatType, Stats};
+
+/// A determinant value, the older version of an IV (0 - 15).
+pub type Dv = u8;
+/// Stat experience, the older version of an EV (0 - 65535).
+pub type StatExp = u16;
+
+/// A [StatSet] of [StatExp]s.
+pub type StatExperience = StatSet<RetroStatType, StatExp>;
+
+/// Which set of formulas is used to calculate stats.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StatSystem {
+    /// IVs, EVs and natures.
+    #[default]
+    Modern,
+    /// DVs and stat experience.
+    Retro,
+}
+
+/// The type of stat in the first and second generation games.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Enum, Serialize, Deserialize,
+)]
+pub enum RetroStatType {
+    Health,
+    Attack,
+    Defense,
+    /// Shared by Special Attack and Special Defense.
+    Special,
+    Speed,
+}
+
+/// The DVs of a pokemon. The health DV is made from the others.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Dvs {
+    pub attack: Dv,
+    pub defense: Dv,
+    pub special: Dv,
+    pub speed: Dv,
+}
+
+impl RetroStatType {
+    /// Get the retro [StatType] a modern [StatType] uses.
+    pub const fn from_modern(stat: StatType) -> Self {
+        match stat {
+            StatType::Health => Self::Health,
+            StatType::Attack => Self::Attack,
+            StatType::Defense => Self::Defense,
+            StatType::SpAttack | StatType::SpDefense => Self::Special,
+            StatType::Speed => Self::Speed,
+        }
+    }
+}
+
+impl Dvs {
+    /// The maximum value of a DV.
+    pub const MAX: Dv = 15;
+
+    /// Generate random DVs.
+    pub fn random(random: &mut impl Rng) -> Self {
+        Self {
+            attack: random.gen_range(0..=Self::MAX),
+            defense: random.gen_range(0..=Self::MAX),
+            special: random.gen_range(0..=Self::MAX),
+            speed: random.gen_range(0..=Self::MAX),
+        }
+    }
+
+    /// The health DV, which is made from the lowest bit of the other DVs.
+    pub const fn health(&self) -> Dv {
+        (self.attack & 1) << 3 | (self.defense & 1) << 2 | (self.speed & 1) << 1 | self.special & 1
+    }
+
+    /// Get the DV of a [RetroStatType].
+    pub const fn get(&self, stat: RetroStatType) -> Dv {
+        match stat {
+            RetroStatType::Health => self.health(),
+            RetroStatType::Attack => self.attack,
+            RetroStatType::Defense => self.defense,
+            RetroStatType::Special => self.special,
+            RetroStatType::Speed => self.speed,
+        }
+    }
+
+    /// Convert IVs to DVs. Special uses the Special Attack IV.
+    pub fn from_ivs(ivs: &Stats) -> Self {
+        let dv = |stat| (ivs[stat] / 2).min(Self::MAX);
+        Self {
+            attack: dv(StatType::Attack),
+            defense: dv(StatType::Defense),
+            special: dv(StatType::SpAttack),
+            speed: dv(StatType::Speed),
+        }
+    }
+
+    /// Convert these DVs to IVs, the same way they are converted when transferring pokemon.
+    pub fn ivs(&self) -> Stats {
+        let mut ivs = Stats::default();
+        for (stat, iv) in ivs.iter_mut() {
+            *iv = self.get(RetroStatType::from_modern(stat)) * 2 + 1;
+        }
+        ivs
+    }
+}
+
+impl StatExperience {
+    /// The maximum value of [StatExp].
+    pub const MAX_STAT_EXP: StatExp = StatExp::MAX;
+
+    /// Convert EVs to stat experience. Special uses the Special Attack EV.
+    pub fn from_evs(evs: &Stats) -> Self {
+        let mut exp = Self::default();
+        for (stat, exp) in exp.iter_mut() {
+            let ev = evs[match stat {
+                RetroStatType::Health => StatType::Health,
+                RetroStatType::Attack => StatType::Attack,
+                RetroStatType::Defense => StatType::Defense,
+                RetroStatType::Special => StatType::SpAttack,
+                RetroStatType::Speed => StatType::Speed,
+            }] as StatExp;
+            *exp = ev * ev;
+        }
+        exp
+    }
+
+    /// Convert this stat experience to EVs that give the same stat bonus.
+    ///
+    /// Stat experience has no total cap, so the EVs are added in [StatType] order
+    /// and stop at [Stats::MAX_EVS_TOTAL] (see [Stats::increment_ev]).
+    pub fn evs(&self) -> Stats {
+        let mut uncapped = Stats::default();
+        for (stat, ev) in uncapped.iter_mut() {
+            *ev = sqrt_ceil(self[RetroStatType::from_modern(stat)]).min(Stats::MAX_EV as u32) as Stat;
+        }
+        let mut evs = Stats::default();
+        for (stat, ev) in uncapped.iter() {
+            evs.increment_ev(stat, *ev);
+        }
+        evs
+    }
+
+    /// The amount stat experience adds to a stat before it is scaled by level.
+    pub fn bonus(exp: StatExp) -> u32 {
+        sqrt_ceil(exp) / 4
+    }
+}
+
+fn sqrt_ceil(n: StatExp) -> u32 {
+    let n = n as u32;
+    let mut root = 0;
+    while root * root < n {
+        root += 1;
+    }
+    root
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::pokemon::{
+        stat::{StatType, Stats},
+        Pokemon,
+    };
+
+    use super::{Dvs, RetroStatType, StatExperience};
+
+    #[test]
+    fn retro_stat() {
+        let dvs = Dvs {
+            attack: 15,
+            defense: 14,
+            special: 15,
+            speed: 15,
+        };
+        assert_eq!(dvs.health(), 0b1011);
+
+        // Mewtwo
+        let max = StatExperience::uniform(StatExperience::MAX_STAT_EXP);
+        let dvs = Dvs {
+            attack: 15,
+            defense: 15,
+            special: 15,
+            speed: 15,
+        };
+        assert_eq!(Pokemon::retro_base_hp(106, dvs.health(), max[RetroStatType::Health], 100), 416);
+        assert_eq!(Pokemon::retro_base_stat(154, dvs.special, 0, 50), 174);
+
+        assert_eq!(Dvs::from_ivs(&dvs.ivs()), dvs);
+        let mut evs = Stats::default();
+        evs[StatType::Attack] = 252;
+        evs[StatType::Speed] = 252;
+        assert_eq!(StatExperience::from_evs(&evs).evs(), evs);
+
+        // EVs stop at the total cap, which stat experience does not have
+        let mut capped = Stats::default();
+        capped[StatType::Health] = 252;
+        capped[StatType::Attack] = 252;
+        capped[StatType::Defense] = 6;
+        assert_eq!(max.evs(), capped);
+        assert_eq!(StatExperience::uniform(65100).evs(), capped);
+        assert_eq!(max.evs().ev_total(), Stats::MAX_EVS_TOTAL);
+    }
+}
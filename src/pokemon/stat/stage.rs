@@ -0,0 +1,195 @@
+//! Stat stages and multipliers that change a pokemon's stats during a battle.
+
+use enum_map::Enum;
+use serde::{Deserialize, Serialize};
+
+use super::{BaseStat, StatSet, StatType};
+
+/// How many stages a stat has been raised or lowered by (-6 to 6).
+pub type Stage = i8;
+
+/// The stats that can be raised or lowered during a battle.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Enum, Serialize, Deserialize,
+)]
+pub enum BattleStatType {
+    Attack,
+    Defense,
+    SpAttack,
+    SpDefense,
+    Speed,
+    Accuracy,
+    Evasion,
+}
+
+/// The stages of every [BattleStatType] of a pokemon in battle.
+pub type StatStages = StatSet<BattleStatType, Stage>;
+
+/// A multiplier for a stat, written as a fraction.
+/// Used by held items and abilities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct StatModifier {
+    pub numerator: u16,
+    pub denominator: u16,
+}
+
+impl BattleStatType {
+    /// Get the [BattleStatType] of a [StatType]. Health does not have one.
+    pub const fn from_stat(stat: StatType) -> Option<Self> {
+        match stat {
+            StatType::Health => None,
+            StatType::Attack => Some(Self::Attack),
+            StatType::Defense => Some(Self::Defense),
+            StatType::SpAttack => Some(Self::SpAttack),
+            StatType::SpDefense => Some(Self::SpDefense),
+            StatType::Speed => Some(Self::Speed),
+        }
+    }
+}
+
+impl StatModifier {
+    /// A modifier that does not change a stat.
+    pub const NONE: Self = Self::new(1, 1);
+
+    pub const fn new(numerator: u16, denominator: u16) -> Self {
+        Self {
+            numerator,
+            denominator,
+        }
+    }
+
+    /// Multiply a stat by this modifier, rounding down.
+    pub fn apply(&self, stat: BaseStat) -> BaseStat {
+        (stat as u32 * self.numerator as u32 / self.denominator.max(1) as u32)
+            .min(BaseStat::MAX as u32) as _
+    }
+
+    /// Get the modifier of a stat stage.
+    /// Accuracy and evasion use thirds instead of halves.
+    pub const fn stage(stat: BattleStatType, stage: Stage) -> Self {
+        let base = match stat {
+            BattleStatType::Accuracy | BattleStatType::Evasion => 3,
+            _ => 2,
+        };
+        let stage = StatStages::clamp(stage);
+        let change = stage.unsigned_abs() as u16;
+        match stage >= 0 {
+            true => Self::new(base + change, base),
+            false => Self::new(base, base + change),
+        }
+    }
+}
+
+impl Default for StatModifier {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl StatStages {
+    /// The lowest a stage can go.
+    pub const MIN_STAGE: Stage = -6;
+    /// The highest a stage can go.
+    pub const MAX_STAGE: Stage = 6;
+
+    const fn clamp(stage: Stage) -> Stage {
+        match stage {
+            stage if stage < Self::MIN_STAGE => Self::MIN_STAGE,
+            stage if stage > Self::MAX_STAGE => Self::MAX_STAGE,
+            stage => stage,
+        }
+    }
+
+    /// Raise (or lower, if negative) a stage.
+    /// Returns how much the stage actually changed.
+    pub fn change(&mut self, stat: BattleStatType, by: Stage) -> Stage {
+        let stage = &mut self[stat];
+        let previous = *stage;
+        *stage = Self::clamp(stage.saturating_add(by));
+        *stage - previous
+    }
+
+    /// Get the modifier of a stage.
+    pub fn modifier(&self, stat: BattleStatType) -> StatModifier {
+        StatModifier::stage(stat, self[stat])
+    }
+
+    /// Get the modifier of a move's accuracy, from the user's accuracy and the target's evasion.
+    /// The combined stage is kept within [StatStages::MIN_STAGE] and [StatStages::MAX_STAGE].
+    pub fn accuracy(&self, target: &Self) -> StatModifier {
+        let stage = self[BattleStatType::Accuracy].saturating_sub(target[BattleStatType::Evasion]);
+        StatModifier::stage(BattleStatType::Accuracy, Self::clamp(stage))
+    }
+
+    /// Get a stat used in battle from a stat given by [Pokemon::stat](crate::pokemon::Pokemon::stat).
+    /// The stage is applied first, and then each modifier from held items, abilities, etc.
+    /// Stats that are not health are never lower than 1.
+    pub fn apply<'a>(
+        &self,
+        stat: StatType,
+        value: BaseStat,
+        modifiers: impl IntoIterator<Item = &'a StatModifier>,
+    ) -> BaseStat {
+        match BattleStatType::from_stat(stat) {
+            Some(battle) => modifiers
+                .into_iter()
+                .fold(self.modifier(battle).apply(value), |value, modifier| {
+                    modifier.apply(value)
+                })
+                .max(1),
+            None => value,
+        }
+    }
+
+    /// Get every stat used in battle (see [StatStages::apply]).
+    /// Modifiers are given for each [StatType].
+    pub fn apply_all<'a, M: IntoIterator<Item = &'a StatModifier>>(
+        &self,
+        stats: &StatSet<StatType, BaseStat>,
+        mut modifiers: impl FnMut(StatType) -> M,
+    ) -> StatSet<StatType, BaseStat> {
+        StatSet::from_fn(|stat| self.apply(stat, stats[stat], modifiers(stat)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::pokemon::stat::{StatSet, StatType};
+
+    use super::{BattleStatType, Stage, StatModifier, StatStages};
+
+    #[test]
+    fn stat_stages() {
+        let mut stages = StatStages::default();
+        assert_eq!(stages.change(BattleStatType::Attack, 2), 2);
+        assert_eq!(stages.change(BattleStatType::Attack, 6), 4);
+        assert_eq!(stages.change(BattleStatType::Speed, -1), -1);
+        assert_eq!(stages.change(BattleStatType::Accuracy, -13), -6);
+
+        assert_eq!(stages.modifier(BattleStatType::Attack), StatModifier::new(8, 2));
+        assert_eq!(stages.modifier(BattleStatType::Speed), StatModifier::new(2, 3));
+        assert_eq!(stages.modifier(BattleStatType::Accuracy), StatModifier::new(3, 9));
+        assert_eq!(stages.accuracy(&StatStages::default()), StatModifier::new(3, 9));
+
+        // stages set out of range do not overflow
+        let mut evasive = StatStages::default();
+        evasive[BattleStatType::Evasion] = Stage::MAX;
+        stages[BattleStatType::Accuracy] = Stage::MIN;
+        assert_eq!(stages.accuracy(&evasive), StatModifier::new(3, 9));
+        stages[BattleStatType::Accuracy] = -6;
+
+        // choice band
+        let band = [StatModifier::new(3, 2)];
+        assert_eq!(stages.apply(StatType::Attack, 100, &band), 600);
+        assert_eq!(stages.apply(StatType::Speed, 100, &[]), 66);
+        assert_eq!(stages.apply(StatType::Health, 100, &band), 100);
+
+        let stats = stages.apply_all(&StatSet::uniform(100), |stat| match stat {
+            StatType::Attack => &band[..],
+            _ => &[],
+        });
+        assert_eq!(stats[StatType::Attack], 600);
+        assert_eq!(stats[StatType::Defense], 100);
+    }
+}
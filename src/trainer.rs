@@ -1,20 +1,14 @@
 use alloc::vec::Vec;
+use core::fmt::{Display, Formatter, Result as FmtResult};
 
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    item::{
-        bag::*,
-        Item,
-    },
+    item::{bag::*, Item, ItemId},
     moves::Move,
-    pokemon::{
-        owned::*,
-        party::Party,
-        Pokemon,
-    },
-    Dex, Money,
+    pokemon::{owned::*, party::Party, Pokemon},
+    DexSet, DexStorage, Money,
 };
 
 type IdInner = tinystr::TinyAsciiStr<16>;
@@ -23,16 +17,50 @@ type IdInner = tinystr::TinyAsciiStr<16>;
 #[serde(transparent)]
 pub struct TrainerGroupId(pub IdInner);
 
+/// The public and secret IDs of a trainer.
+/// These decide which of a trainer's pokemon are shiny.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct TrainerId {
+    pub id: u16,
+    pub secret: u16,
+}
+
 pub type TrainerData = Trainer<UserPokemonData, BagData>;
 pub type UserTrainer = Trainer<UserPokemon, UserBag>;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Trainer<P, B> {
+    #[serde(default)]
+    pub id: TrainerId,
     pub party: Party<P>,
     pub bag: B,
     pub money: Money,
 }
 
+/// Every problem found when initializing a [TrainerData].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TrainerError {
+    /// Pokemon in the party that could not be initialized, with their position in the party and every problem found.
+    pub party: Vec<(usize, Vec<UserPokemonError>)>,
+    /// Items in the bag that are not in the itemdex.
+    pub bag: Vec<ItemId>,
+}
+
+impl Display for TrainerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Could not initialize trainer:")?;
+        for (index, errors) in self.party.iter() {
+            for error in errors {
+                write!(f, "\n  party pokemon {}: {}", index, error)?;
+            }
+        }
+        for id in self.bag.iter() {
+            write!(f, "\n  bag: Could not find item {}", id)?;
+        }
+        Ok(())
+    }
+}
+
 impl From<IdInner> for TrainerGroupId {
     fn from(inner: IdInner) -> Self {
         Self(inner)
@@ -50,6 +78,7 @@ impl core::str::FromStr for TrainerGroupId {
 impl<P, B: Default> Default for Trainer<P, B> {
     fn default() -> Self {
         Self {
+            id: Default::default(),
             party: Default::default(),
             bag: Default::default(),
             money: Default::default(),
@@ -58,24 +87,37 @@ impl<P, B: Default> Default for Trainer<P, B> {
 }
 
 impl TrainerData {
+    /// Initialize this trainer.
+    /// Fails with every problem in the party and bag, rather than just the first one.
     pub fn init(
         self,
         random: &mut impl Rng,
-        pokedex: &Dex<Pokemon>,
-        movedex: &Dex<Move>,
-        itemdex: &Dex<Item>,
-    ) -> Option<UserTrainer> {
-        Some(UserTrainer {
-            party: {
-                let mut party = Vec::new();
-                for pokemon in self.party {
-                    party.push(pokemon.init(pokedex, movedex, itemdex, Some(random)).ok()?);
-                }
-                party
-            },
-            bag: self.bag.init(itemdex)?,
-            money: self.money,
-        })
+        dexes: &DexSet<impl DexStorage<Pokemon>, impl DexStorage<Move>, impl DexStorage<Item>>,
+    ) -> Result<UserTrainer, TrainerError> {
+        let mut error = TrainerError::default();
+
+        let mut party = Vec::with_capacity(self.party.len());
+        for (index, pokemon) in self.party.iter().enumerate() {
+            match pokemon.init(dexes, Some(random)) {
+                Ok(pokemon) => party.push(pokemon),
+                Err(e) => error.party.push((index, e)),
+            }
+        }
+
+        let bag = self.bag.init(&dexes.itemdex).unwrap_or_else(|missing| {
+            error.bag = missing;
+            Default::default()
+        });
+
+        match error.party.is_empty() && error.bag.is_empty() {
+            true => Ok(UserTrainer {
+                id: self.id,
+                party,
+                bag,
+                money: self.money,
+            }),
+            false => Err(error),
+        }
     }
 }
 
@@ -83,6 +125,7 @@ impl UserTrainer
 {
     pub fn data(&self) -> TrainerData {
         TrainerData {
+            id: self.id,
             party: self.party.iter().map(|p| p.data()).collect(),
             bag: self.bag.data(),
             money: self.money,
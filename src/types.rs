@@ -5,8 +5,9 @@ use core::{
 use serde::{Deserialize, Serialize};
 
 /// Pokemon types
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub enum PokemonType {
+    #[default]
     Unknown,
 
     Normal,
@@ -30,8 +31,9 @@ pub enum PokemonType {
 }
 
 /// Pokemon Type effectiveness
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub enum Effective {
+    #[default]
     Effective,
     Ineffective,
     NotEffective,
@@ -57,18 +59,6 @@ impl Effective {
     }
 }
 
-impl Default for PokemonType {
-    fn default() -> Self {
-        Self::Unknown
-    }
-}
-
-impl Default for Effective {
-    fn default() -> Self {
-        Self::Effective
-    }
-}
-
 impl Mul for Effective {
     type Output = Self;
 
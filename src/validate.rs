@@ -0,0 +1,211 @@
+//! Checks that dexes only refer to entries that exist.
+
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::{
+    item::{Item, ItemId},
+    moves::{Move, MoveId},
+    pokemon::{data::EvolutionType, Pokemon, PokemonId},
+    DexSet, DexStorage,
+};
+
+/// Which Dex a problem was found in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DexKind {
+    Pokemon,
+    Move,
+    Item,
+}
+
+/// A problem found when validating dexes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DexIssue {
+    /// A Dex does not have its unknown entry, so [Dex::unknown](crate::Dex::unknown) and [Dex::get](crate::Dex::get) would panic.
+    MissingUnknown(DexKind),
+    /// A pokemon can learn a move that is not in the movedex.
+    MissingMove { pokemon: PokemonId, id: MoveId },
+    /// A pokemon evolves into a pokemon that is not in the pokedex.
+    MissingEvolution { pokemon: PokemonId, id: PokemonId },
+    /// A pokemon evolves with an item that is not in the itemdex.
+    MissingEvolutionItem { pokemon: PokemonId, id: ItemId },
+}
+
+/// Every problem found when validating dexes.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DexReport {
+    pub issues: Vec<DexIssue>,
+}
+
+impl DexReport {
+    /// Check if no problems were found.
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Turn this report into a [Result], failing if any problems were found.
+    pub fn result(self) -> Result<(), Self> {
+        match self.is_ok() {
+            true => Ok(()),
+            false => Err(self),
+        }
+    }
+}
+
+/// Check that every reference between dexes points to an entry that exists,
+/// and that every Dex has its unknown entry.
+pub fn validate(
+    dexes: &DexSet<impl DexStorage<Pokemon>, impl DexStorage<Move>, impl DexStorage<Item>>,
+) -> DexReport {
+    let mut issues = Vec::new();
+
+    if !dexes.pokedex.has_unknown() {
+        issues.push(DexIssue::MissingUnknown(DexKind::Pokemon));
+    }
+    if !dexes.movedex.has_unknown() {
+        issues.push(DexIssue::MissingUnknown(DexKind::Move));
+    }
+    if !dexes.itemdex.has_unknown() {
+        issues.push(DexIssue::MissingUnknown(DexKind::Item));
+    }
+
+    for pokemon in dexes.pokedex.values() {
+        let learnset = &pokemon.learnset;
+        let moves = pokemon
+            .moves
+            .iter()
+            .map(|m| &m.1)
+            .chain(learnset.machine.iter())
+            .chain(learnset.tutor.iter())
+            .chain(learnset.egg.iter())
+            .chain(learnset.event.iter());
+
+        for id in moves {
+            if dexes.movedex.try_get(id).is_none() {
+                issues.push(DexIssue::MissingMove {
+                    pokemon: pokemon.id,
+                    id: *id,
+                });
+            }
+        }
+
+        if let Some(evolution) = &pokemon.evolution {
+            if dexes.pokedex.try_get(&evolution.1).is_none() {
+                issues.push(DexIssue::MissingEvolution {
+                    pokemon: pokemon.id,
+                    id: evolution.1,
+                });
+            }
+            if let EvolutionType::Item(item) = &evolution.0 {
+                if dexes.itemdex.try_get(item).is_none() {
+                    issues.push(DexIssue::MissingEvolutionItem {
+                        pokemon: pokemon.id,
+                        id: *item,
+                    });
+                }
+            }
+        }
+    }
+
+    issues.sort();
+    issues.dedup();
+
+    DexReport { issues }
+}
+
+impl Display for DexIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::MissingUnknown(kind) => write!(f, "{:?} dex is missing its unknown entry", kind),
+            Self::MissingMove { pokemon, id } => {
+                write!(f, "Pokemon {} learns missing move \"{}\"", pokemon, id.0)
+            }
+            Self::MissingEvolution { pokemon, id } => {
+                write!(f, "Pokemon {} evolves into missing pokemon {}", pokemon, id)
+            }
+            Self::MissingEvolutionItem { pokemon, id } => {
+                write!(f, "Pokemon {} evolves with missing item {}", pokemon, id)
+            }
+        }
+    }
+}
+
+impl Display for DexReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Found {} problem(s) in dexes", self.issues.len())?;
+        for issue in self.issues.iter() {
+            write!(f, "\n  {}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use alloc::vec;
+
+    use crate::{
+        dex::tests::test_move,
+        item::ItemId,
+        moves::{Move, MoveId},
+        pokemon::{
+            data::{Evolution, EvolutionType, LearnableMove},
+            tests::test_pokemon,
+            Pokemon, PokemonId,
+        },
+        Dex, DexSet, Identifiable,
+    };
+
+    use super::{DexIssue, DexKind};
+
+    #[test]
+    fn validate() {
+        let tackle: MoveId = "tackle".parse().unwrap();
+        let surf: MoveId = "surf".parse().unwrap();
+        let stone: ItemId = "stone".parse().unwrap();
+
+        let mut pokemon = test_pokemon(vec![LearnableMove(1, tackle)]);
+        pokemon.learnset.machine.push(surf);
+        pokemon.evolution = Some(Evolution(EvolutionType::Item(stone), PokemonId(2)));
+
+        let mut pokedex = Dex::<Pokemon>::default();
+        pokedex.insert(pokemon);
+
+        let mut movedex = Dex::<Move>::default();
+        for id in [Move::UNKNOWN, tackle] {
+            movedex.insert(test_move(id.0.as_str(), id.0.as_str()));
+        }
+
+        let dexes: DexSet = DexSet {
+            pokedex,
+            movedex,
+            ..Default::default()
+        };
+
+        let report = dexes.validate();
+
+        let saved = serde_json::to_string(&dexes).unwrap();
+        let loaded = serde_json::from_str::<DexSet>(&saved).unwrap();
+        assert_eq!(loaded.validate(), report);
+        assert_eq!(
+            report.issues,
+            [
+                DexIssue::MissingUnknown(DexKind::Item),
+                DexIssue::MissingMove {
+                    pokemon: PokemonId(0),
+                    id: surf
+                },
+                DexIssue::MissingEvolution {
+                    pokemon: PokemonId(0),
+                    id: PokemonId(2)
+                },
+                DexIssue::MissingEvolutionItem {
+                    pokemon: PokemonId(0),
+                    id: stone
+                },
+            ]
+        );
+        assert!(report.result().is_err());
+    }
+}